@@ -1,13 +1,19 @@
 use crate::{
     field::RecordFields,
-    fmt::{format, FormatEvent, FormatFields, MakeWriter, TestWriter},
+    fmt::{
+        format,
+        internal_error::{InternalError, InternalErrorHandler},
+        sink::SinkHandle,
+        writer::BoxMakeWriter,
+        FormatEvent, FormatFields, MakeWriter, Sink, TestWriter, WriterRouter,
+    },
     registry::{self, LookupSpan, SpanRef},
     subscribe::{self, Context},
 };
 use format::{FmtSpan, TimingDisplay};
 use std::{
     any::TypeId, cell::RefCell, env, fmt, io, marker::PhantomData, ops::Deref, ptr::NonNull,
-    time::Instant,
+    sync::Arc, time::Instant,
 };
 use tracing_core::{
     field,
@@ -69,9 +75,191 @@ pub struct Subscriber<C, N = format::DefaultFields, E = format::Format, W = fn()
     fmt_span: format::FmtSpanConfig,
     is_ansi: bool,
     log_internal_errors: bool,
+    field_redactor: Option<FieldRedactor>,
+    writer_router: Option<WriterRouter>,
+    sink: Option<SinkHandle>,
+    on_internal_error: Option<InternalErrorHandler>,
+    span_timing_stats: bool,
+    timing_mode: TimingMode,
+    field_reformatting: bool,
+    span_event_rules: SpanEventRules,
     _inner: PhantomData<fn(C)>,
 }
 
+/// A closure that masks sensitive field values before they are written by a
+/// [`FormatFields`] implementation.
+///
+/// Constructed by [`Subscriber::with_field_redactor`].
+#[derive(Clone)]
+struct FieldRedactor(Arc<dyn Fn(&field::Field, &mut dyn fmt::Write) -> bool + Send + Sync>);
+
+impl fmt::Debug for FieldRedactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("FieldRedactor(..)")
+    }
+}
+
+/// Per-level/target overrides for which [`FmtSpan`] lifecycle events are
+/// synthesized, layered on top of a [`Subscriber`]'s default.
+///
+/// Built up incrementally with [`Subscriber::with_span_events_for`]; there
+/// is no public constructor, since a rule is meaningless without the
+/// `Subscriber` it's attached to.
+#[derive(Default)]
+struct SpanEventRules {
+    rules: Vec<(Box<dyn Fn(&Metadata<'_>) -> bool + Send + Sync>, FmtSpan)>,
+}
+
+impl SpanEventRules {
+    /// Returns the `FmtSpan` for the first rule matching `meta`, or
+    /// `default` if none match.
+    fn kind_for(&self, meta: &Metadata<'_>, default: FmtSpan) -> FmtSpan {
+        self.rules
+            .iter()
+            .find(|(filter, _)| filter(meta))
+            .map_or(default, |(_, kind)| *kind)
+    }
+}
+
+impl fmt::Debug for SpanEventRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpanEventRules")
+            .field("rules", &self.rules.len())
+            .finish()
+    }
+}
+
+/// A field value captured while re-visiting fields for redaction.
+///
+/// Fields the redactor declines to mask keep their original typed
+/// representation (`F64`/`I64`/`U64`/`Bool`/`Str`) so that a downstream
+/// [`FormatFields`] implementation (e.g. a JSON formatter) sees the same
+/// value it would have without redaction enabled. Only masked values, and
+/// values that only ever had a `Debug` representation to begin with, are
+/// flattened to `Rendered` text.
+enum MaskedValue {
+    F64(f64),
+    I64(i64),
+    U64(u64),
+    Bool(bool),
+    Str(String),
+    Rendered(String),
+}
+
+impl field::Value for MaskedValue {
+    fn record(&self, key: &field::Field, visitor: &mut dyn field::Visit) {
+        match self {
+            MaskedValue::F64(v) => visitor.record_f64(key, *v),
+            MaskedValue::I64(v) => visitor.record_i64(key, *v),
+            MaskedValue::U64(v) => visitor.record_u64(key, *v),
+            MaskedValue::Bool(v) => visitor.record_bool(key, *v),
+            MaskedValue::Str(v) => visitor.record_str(key, v),
+            MaskedValue::Rendered(v) => field::display(v).record(key, visitor),
+        }
+    }
+}
+
+/// Formats `fields` using `fmt_fields`, giving `redactor` the chance to
+/// replace each field's value before it is written.
+///
+/// This re-visits `fields`, letting `redactor` mask any value it chooses
+/// to, and re-records the result as a synthetic [`Event`]: masked fields
+/// are re-recorded as the redactor's rendered text, while every other
+/// field is re-recorded with its original typed value untouched, so that
+/// `fmt_fields` sees exactly what it would have without redaction enabled.
+fn format_fields_redacted<'writer, N, R>(
+    fmt_fields: &N,
+    writer: format::Writer<'writer>,
+    fields: R,
+    parent: Option<Id>,
+    meta: &'static Metadata<'static>,
+    redactor: &FieldRedactor,
+) -> fmt::Result
+where
+    N: FormatFields<'writer>,
+    R: RecordFields,
+{
+    struct Collector<'a> {
+        redactor: &'a FieldRedactor,
+        values: Vec<(&'static str, MaskedValue)>,
+    }
+
+    impl Collector<'_> {
+        /// Gives the redactor a chance to mask `field`, returning its
+        /// rendered replacement if it chose to.
+        fn masked(&self, field: &field::Field) -> Option<String> {
+            let mut masked = String::new();
+            (self.redactor.0)(field, &mut masked).then_some(masked)
+        }
+
+        fn push(&mut self, field: &field::Field, value: MaskedValue) {
+            self.values.push((field.name(), value));
+        }
+    }
+
+    impl field::Visit for Collector<'_> {
+        fn record_f64(&mut self, field: &field::Field, value: f64) {
+            let value = self
+                .masked(field)
+                .map_or(MaskedValue::F64(value), MaskedValue::Rendered);
+            self.push(field, value);
+        }
+
+        fn record_i64(&mut self, field: &field::Field, value: i64) {
+            let value = self
+                .masked(field)
+                .map_or(MaskedValue::I64(value), MaskedValue::Rendered);
+            self.push(field, value);
+        }
+
+        fn record_u64(&mut self, field: &field::Field, value: u64) {
+            let value = self
+                .masked(field)
+                .map_or(MaskedValue::U64(value), MaskedValue::Rendered);
+            self.push(field, value);
+        }
+
+        fn record_bool(&mut self, field: &field::Field, value: bool) {
+            let value = self
+                .masked(field)
+                .map_or(MaskedValue::Bool(value), MaskedValue::Rendered);
+            self.push(field, value);
+        }
+
+        fn record_str(&mut self, field: &field::Field, value: &str) {
+            let value = self
+                .masked(field)
+                .map_or_else(|| MaskedValue::Str(value.to_owned()), MaskedValue::Rendered);
+            self.push(field, value);
+        }
+
+        fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+            let rendered = self
+                .masked(field)
+                .unwrap_or_else(|| format!("{:?}", value));
+            self.push(field, MaskedValue::Rendered(rendered));
+        }
+    }
+
+    let mut collector = Collector {
+        redactor,
+        values: Vec::new(),
+    };
+    fields.record(&mut collector);
+
+    let names: Vec<&'static str> = collector.values.iter().map(|(name, _)| *name).collect();
+    let fs = field::FieldSet::new(&names, meta.callsite());
+    let mut iter = fs.iter();
+    let values: Vec<_> = collector
+        .values
+        .iter()
+        .map(|(_, value)| (iter.next().unwrap(), Some(value as &dyn field::Value)))
+        .collect();
+    let vs = fs.value_set(&values);
+    let event = Event::new_child_of(parent, meta, &vs);
+    fmt_fields.format_fields(writer, &event)
+}
+
 impl<C> Subscriber<C> {
     /// Returns a new [`Subscriber`] with the default configuration.
     pub fn new() -> Self {
@@ -119,6 +307,14 @@ where
             make_writer: self.make_writer,
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -149,6 +345,14 @@ where
             make_writer: self.make_writer,
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -186,6 +390,14 @@ impl<C, N, E, W> Subscriber<C, N, E, W> {
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
             make_writer,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -261,6 +473,43 @@ impl<C, N, E, W> Subscriber<C, N, E, W> {
         }
     }
 
+    /// Adds a rule overriding which span lifecycle events are synthesized
+    /// for spans whose [`Metadata`] matches `filter`, layered on top of the
+    /// default configured by [`Self::with_span_events`] (or
+    /// [`Self::set_span_events`]).
+    ///
+    /// Rules are consulted, in the order they were added, each time a span
+    /// is created, entered, exited, or closed; the first matching rule
+    /// wins, and spans matching
+    /// none of them fall back to the default `FmtSpan`. This avoids
+    /// drowning high-level spans in `ENTER`/`EXIT` lines while keeping
+    /// detailed tracing on hot paths.
+    ///
+    /// # Examples
+    ///
+    /// Full lifecycle tracing for the `myapp::db` targets, but only
+    /// `CLOSE` events everywhere else:
+    ///
+    /// ```rust
+    /// use tracing_subscriber::fmt::{self, format::FmtSpan};
+    ///
+    /// let subscriber = fmt::subscriber()
+    ///     .with_span_events(FmtSpan::CLOSE)
+    ///     .with_span_events_for(|meta| meta.target().starts_with("myapp::db"), FmtSpan::FULL);
+    /// # use tracing_subscriber::Subscribe as _;
+    /// # let _ = subscriber.with_collector(tracing_subscriber::registry::Registry::default());
+    /// ```
+    ///
+    /// [`Metadata`]: tracing_core::Metadata
+    pub fn with_span_events_for(
+        mut self,
+        filter: impl Fn(&Metadata<'_>) -> bool + Send + Sync + 'static,
+        kind: FmtSpan,
+    ) -> Self {
+        self.span_event_rules.rules.push((Box::new(filter), kind));
+        self
+    }
+
     /// Configures the subscriber to support [`libtest`'s output capturing][capturing] when used in
     /// unit tests.
     ///
@@ -291,6 +540,14 @@ impl<C, N, E, W> Subscriber<C, N, E, W> {
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
             make_writer: TestWriter::default(),
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -357,6 +614,176 @@ impl<C, N, E, W> Subscriber<C, N, E, W> {
         }
     }
 
+    /// Sets a callback to be invoked when formatting or writing an event or a
+    /// span's fields fails, instead of the default behavior of printing the
+    /// failure to stderr (gated by [`log_internal_errors`]).
+    ///
+    /// This is useful in production, where stdout/stderr may be the very
+    /// sink that failed to accept a write, or where formatter/writer
+    /// failures should be routed to a metrics counter or a fallback sink
+    /// rather than spamming stderr.
+    ///
+    /// Setting a handler with this method overrides the
+    /// [`log_internal_errors`] setting; the handler is invoked unconditionally.
+    ///
+    /// [`log_internal_errors`]: Subscriber::log_internal_errors
+    pub fn on_internal_error(
+        self,
+        handler: impl Fn(&InternalError<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            on_internal_error: Some(InternalErrorHandler(Arc::new(handler))),
+            ..self
+        }
+    }
+
+    /// Sets a callback to be invoked specifically when a span's fields or an
+    /// event fail to *format* (that is, [`InternalError::FormatField`] or
+    /// [`InternalError::FormatEvent`]), instead of the default behavior of
+    /// printing the failure to stderr (gated by [`log_internal_errors`]).
+    ///
+    /// This is a narrower, more convenient alternative to
+    /// [`on_internal_error`] for applications that only care about `Debug`/
+    /// `Display` failures and not, say, failures to write to the configured
+    /// writer — to increment a metrics counter, send the failure to a
+    /// dead-letter sink, or `panic!` in tests when a field's formatting
+    /// implementation misbehaves.
+    ///
+    /// Setting a handler with this method overrides the
+    /// [`log_internal_errors`] setting, and replaces any handler
+    /// previously set with [`on_internal_error`]; the handler is invoked
+    /// unconditionally.
+    ///
+    /// [`log_internal_errors`]: Subscriber::log_internal_errors
+    /// [`on_internal_error`]: Subscriber::on_internal_error
+    pub fn on_format_error(
+        self,
+        handler: impl Fn(&Metadata<'static>, fmt::Error) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_internal_error(move |error| match error {
+            InternalError::FormatField(meta, e) | InternalError::FormatEvent(meta, e) => {
+                handler(meta, *e)
+            }
+            InternalError::WriteEvent(..) => {}
+        })
+    }
+
+    /// Sets a closure that is given the chance to mask a field's value before
+    /// it is written by this subscriber's [`FormatFields`] implementation.
+    ///
+    /// The closure is called once per field, for both event fields and the
+    /// fields recorded on spans, with the [`Field`][field] being recorded
+    /// and a writer to mask the value into. Returning `true` substitutes
+    /// whatever was written into the provided writer for the field's real
+    /// value; returning `false` leaves the real value untouched.
+    ///
+    /// This is the only supported way to keep secrets recorded as tracing
+    /// fields (e.g. `password`, `authorization`) out of formatted output
+    /// without having to avoid recording them at the call site.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::fmt::Write;
+    /// use tracing_subscriber::fmt;
+    ///
+    /// let subscriber = fmt::subscriber().with_field_redactor(|field, writer| {
+    ///     if field.name() == "password" {
+    ///         let _ = write!(writer, "***");
+    ///         true
+    ///     } else {
+    ///         false
+    ///     }
+    /// });
+    /// # // this is necessary for type inference.
+    /// # use tracing_subscriber::Subscribe as _;
+    /// # let _ = subscriber.with_collector(tracing_subscriber::registry::Registry::default());
+    /// ```
+    ///
+    /// [field]: tracing_core::field::Field
+    pub fn with_field_redactor(
+        self,
+        redactor: impl Fn(&field::Field, &mut dyn fmt::Write) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            field_redactor: Some(FieldRedactor(Arc::new(redactor))),
+            ..self
+        }
+    }
+
+    /// Sets a [`WriterRouter`] that the [`Subscriber`] being built will consult,
+    /// per event, to choose which writer to send formatted output to.
+    ///
+    /// This supersedes the configured [`MakeWriter`][Self::with_writer] for
+    /// events that match one of the router's routes; events that match none
+    /// of them fall back to the router's default writer. All events still
+    /// share a single event formatter and span store.
+    ///
+    /// # Examples
+    ///
+    /// Sending `ERROR` events to stderr and everything else to stdout:
+    ///
+    /// ```rust
+    /// use tracing::Level;
+    /// use tracing_subscriber::fmt::{self, WriterRouter};
+    ///
+    /// let subscriber = fmt::subscriber().with_writer_router(
+    ///     WriterRouter::new(std::io::stdout)
+    ///         .route(|meta| *meta.level() == Level::ERROR, std::io::stderr),
+    /// );
+    /// # // this is necessary for type inference.
+    /// # use tracing_subscriber::Subscribe as _;
+    /// # let _ = subscriber.with_collector(tracing_subscriber::registry::Registry::default());
+    /// ```
+    ///
+    /// [`Subscriber`]: super::Subscriber
+    pub fn with_writer_router(self, router: WriterRouter) -> Self {
+        Self {
+            writer_router: Some(router),
+            ..self
+        }
+    }
+
+    /// Sets a [`Sink`] that receives every already-formatted event (and, if
+    /// [span events] are enabled, every synthesized span lifecycle
+    /// notification), instead of writing formatted bytes to a [`MakeWriter`].
+    ///
+    /// This supersedes the configured [`MakeWriter`][Self::with_writer] (and
+    /// any [`WriterRouter`][Self::with_writer_router]) entirely: once a
+    /// `Sink` is set, this subscriber stops writing anywhere on its own and
+    /// calls [`Sink::on_record`] instead, handing it the rendered text
+    /// alongside the event's (or span's) [`Metadata`] so that it can make
+    /// its own decisions about rotation, buffering, or routing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tracing_core::Metadata;
+    /// use tracing_subscriber::fmt::{self, Sink};
+    ///
+    /// #[derive(Debug)]
+    /// struct PrintingSink;
+    ///
+    /// impl Sink for PrintingSink {
+    ///     fn on_record(&self, _meta: &Metadata<'_>, formatted: &str) {
+    ///         print!("{}", formatted);
+    ///     }
+    /// }
+    ///
+    /// let subscriber = fmt::subscriber().with_sink(PrintingSink);
+    /// # // this is necessary for type inference.
+    /// # use tracing_subscriber::Subscribe as _;
+    /// # let _ = subscriber.with_collector(tracing_subscriber::registry::Registry::default());
+    /// ```
+    ///
+    /// [span events]: Self::with_span_events
+    pub fn with_sink(self, sink: impl Sink) -> Self {
+        Self {
+            sink: Some(SinkHandle(Arc::new(sink))),
+            ..self
+        }
+    }
+
     /// Updates the [`MakeWriter`] by applying a function to the existing [`MakeWriter`].
     ///
     /// This sets the [`MakeWriter`] that the subscriber being built will use to write events.
@@ -387,6 +814,14 @@ impl<C, N, E, W> Subscriber<C, N, E, W> {
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
             make_writer: f(self.make_writer),
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -418,6 +853,14 @@ where
             make_writer: self.make_writer,
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -431,6 +874,14 @@ where
             make_writer: self.make_writer,
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -483,6 +934,58 @@ where
         }
     }
 
+    /// Sets how a `CLOSE` span event reports the time the span spent alive.
+    ///
+    /// By default ([`TimingMode::BusyIdle`]), `time.busy` and `time.idle`
+    /// are reported as separate fields, so that the time spent actually
+    /// running can be told apart from time spent waiting. Set this to
+    /// [`TimingMode::Total`] to instead report a single combined
+    /// `time.busy` field, if the idle/busy split isn't useful for a
+    /// particular subscriber.
+    pub fn with_span_timing_mode(self, timing_mode: TimingMode) -> Self {
+        Subscriber {
+            timing_mode,
+            ..self
+        }
+    }
+
+    /// Sets whether `CLOSE` span events should include, in addition to the
+    /// span's total `time.busy`/`time.idle`, the number of distinct busy
+    /// periods (outermost enters; re-entering an already-entered span is not
+    /// counted) as `time.enters`, and the shortest/longest single busy
+    /// period (`time.busy.min`/`time.busy.max`).
+    ///
+    /// This is disabled by default, since it costs an extra two
+    /// comparisons per [exit]. Enabling it is useful for spotting spans
+    /// that are cheap on average but occasionally stall.
+    ///
+    /// [exit]: mod@tracing::span#the-span-lifecycle
+    pub fn with_span_timing_stats(self, span_timing_stats: bool) -> Self {
+        Subscriber {
+            span_timing_stats,
+            ..self
+        }
+    }
+
+    /// Sets whether a span's originally recorded field values are retained
+    /// so that they can later be re-rendered by a different
+    /// [`FormatFields`] implementation, via [`FmtContext::reformat_span_fields`].
+    ///
+    /// This is disabled by default, since it costs an extra allocation per
+    /// recorded field for every live span. Enable it when a single
+    /// [`Collect`][collect] drives multiple `fmt` subscribers with
+    /// different formatters (for example, one ANSI terminal subscriber and
+    /// one plain-text file subscriber) and each one needs its own
+    /// rendering of a span's fields.
+    ///
+    /// [collect]: tracing_core::Collect
+    pub fn with_field_reformatting(self, field_reformatting: bool) -> Self {
+        Subscriber {
+            field_reformatting,
+            ..self
+        }
+    }
+
     /// Sets whether or not an event's target is displayed.
     pub fn with_target(self, display_target: bool) -> Subscriber<C, N, format::Format<L, T>, W> {
         Subscriber {
@@ -563,6 +1066,14 @@ where
             make_writer: self.make_writer,
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -578,6 +1089,14 @@ where
             make_writer: self.make_writer,
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -608,6 +1127,56 @@ where
             // always disable ANSI escapes in JSON mode!
             is_ansi: false,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
+            _inner: self._inner,
+        }
+    }
+
+    /// Sets the subscriber being built to use a [logfmt formatter](format::Logfmt).
+    ///
+    /// [logfmt] renders each event as a single line of space-separated
+    /// `key=value` pairs (level, target, timestamp, and message are included
+    /// as ordinary keys), quoting and escaping any value that contains a
+    /// space, a quote, or an `=`. Fields recorded on entered spans are
+    /// flattened in as additional keys, each prefixed with `span.` to avoid
+    /// colliding with event fields (or other spans' fields) of the same
+    /// name.
+    ///
+    /// This format is convenient for feeding into log-ingestion pipelines
+    /// (such as Loki, Heroku's router, or Splunk) that parse `key=value`
+    /// lines natively.
+    ///
+    /// # Example Output
+    ///
+    /// ```ignore
+    /// level=info target=mycrate span.request_id=42 message="some message" latency_ms=12
+    /// ```
+    ///
+    /// [logfmt]: https://brandur.org/logfmt
+    pub fn logfmt(self) -> Subscriber<C, format::LogfmtFields, format::Format<format::Logfmt, T>, W> {
+        Subscriber {
+            fmt_event: self.fmt_event.logfmt(),
+            fmt_fields: format::LogfmtFields::new(),
+            fmt_span: self.fmt_span,
+            make_writer: self.make_writer,
+            // logfmt has no notion of ANSI colors.
+            is_ansi: false,
+            log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -675,6 +1244,14 @@ impl<C, N, E, W> Subscriber<C, N, E, W> {
             make_writer: self.make_writer,
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -706,6 +1283,14 @@ impl<C, N, E, W> Subscriber<C, N, E, W> {
             make_writer: self.make_writer,
             is_ansi: self.is_ansi,
             log_internal_errors: self.log_internal_errors,
+            field_redactor: self.field_redactor,
+            writer_router: self.writer_router,
+            sink: self.sink,
+            on_internal_error: self.on_internal_error,
+            span_timing_stats: self.span_timing_stats,
+            timing_mode: self.timing_mode,
+            field_reformatting: self.field_reformatting,
+            span_event_rules: self.span_event_rules,
             _inner: self._inner,
         }
     }
@@ -724,6 +1309,14 @@ impl<C> Default for Subscriber<C> {
             make_writer: io::stdout,
             is_ansi: ansi,
             log_internal_errors: false,
+            field_redactor: None,
+            writer_router: None,
+            sink: None,
+            on_internal_error: None,
+            span_timing_stats: false,
+            timing_mode: TimingMode::default(),
+            field_reformatting: false,
+            span_event_rules: SpanEventRules::default(),
             _inner: PhantomData,
         }
     }
@@ -742,8 +1335,44 @@ where
             ctx,
             fmt_fields: &self.fmt_fields,
             event,
+            field_redactor: self.field_redactor.as_ref(),
+        }
+    }
+
+    /// Returns the writer that an event or span lifecycle notification with
+    /// `meta` should be written to, consulting the configured
+    /// [`WriterRouter`], if any, before falling back to the [`MakeWriter`].
+    fn writer_for<'a>(&'a self, meta: &Metadata<'_>) -> Box<dyn io::Write + 'a> {
+        match &self.writer_router {
+            Some(router) => router.make_writer_for(meta),
+            None => Box::new(self.make_writer.make_writer_for(meta)),
         }
     }
+
+    /// Like [`writer_for`][Self::writer_for], but for a synthesized span
+    /// lifecycle event of the given `kind`, allowing it to be routed
+    /// differently than the span's own events (for example, sending
+    /// timing-bearing `CLOSE` events to one sink and `ENTER`/`EXIT` noise to
+    /// another).
+    fn writer_for_span_event<'a>(
+        &'a self,
+        meta: &Metadata<'_>,
+        kind: FmtSpan,
+    ) -> Box<dyn io::Write + 'a> {
+        match &self.writer_router {
+            Some(router) => router.make_writer_for_span_event(meta, kind),
+            None => Box::new(self.make_writer.make_writer_for_span_event(meta, kind)),
+        }
+    }
+
+    /// Returns the [`FmtSpan`] lifecycle events that should be synthesized
+    /// for a span whose metadata is `meta`, consulting any per-level/target
+    /// rules added with [`Subscriber::with_span_events_for`] before falling
+    /// back to the subscriber's default (set with
+    /// [`Subscriber::with_span_events`] or [`Subscriber::set_span_events`]).
+    fn span_events_for(&self, meta: &Metadata<'_>) -> FmtSpan {
+        self.span_event_rules.kind_for(meta, self.fmt_span.kind)
+    }
 }
 
 /// A formatted representation of a span's fields stored in its [extensions].
@@ -805,6 +1434,96 @@ impl<E: ?Sized> Deref for FormattedFields<E> {
     }
 }
 
+/// A typed field value retained by [`StoredFields`].
+///
+/// Mirrors [`MaskedValue`]'s typed variants: a field keeps its original
+/// `F64`/`I64`/`U64`/`Bool`/`Str` representation so that a downstream
+/// [`FormatFields`] implementation (e.g. a JSON formatter) sees the same
+/// typed value it would have without reformatting, rather than the
+/// `Debug`-rendered string every field used to be flattened to. Only fields
+/// with no typed representation to begin with (recorded via `record_debug`)
+/// fall back to `Rendered` text.
+#[derive(Debug)]
+enum StoredValue {
+    F64(f64),
+    I64(i64),
+    U64(u64),
+    Bool(bool),
+    Str(String),
+    Rendered(String),
+}
+
+impl field::Value for StoredValue {
+    fn record(&self, key: &field::Field, visitor: &mut dyn field::Visit) {
+        match self {
+            StoredValue::F64(v) => visitor.record_f64(key, *v),
+            StoredValue::I64(v) => visitor.record_i64(key, *v),
+            StoredValue::U64(v) => visitor.record_u64(key, *v),
+            StoredValue::Bool(v) => visitor.record_bool(key, *v),
+            StoredValue::Str(v) => visitor.record_str(key, v),
+            StoredValue::Rendered(v) => field::display(v).record(key, visitor),
+        }
+    }
+}
+
+/// The field values originally recorded for a span, retained (alongside any
+/// [`FormattedFields`]) so that a different [`FormatFields`] implementation
+/// can later re-render them via [`FmtContext::reformat_span_fields`].
+///
+/// Unlike [`FormattedFields`], this is not generic over a formatter type:
+/// there is only ever one `StoredFields` per span, since the values it
+/// holds aren't tied to how they were rendered.
+#[derive(Debug, Default)]
+struct StoredFields {
+    values: Vec<(field::Field, StoredValue)>,
+}
+
+impl StoredFields {
+    fn record(&mut self, fields: impl RecordFields) {
+        let mut visitor = StoredFieldsVisitor(&mut self.values);
+        fields.record(&mut visitor);
+    }
+}
+
+struct StoredFieldsVisitor<'a>(&'a mut Vec<(field::Field, StoredValue)>);
+
+impl StoredFieldsVisitor<'_> {
+    fn push(&mut self, field: &field::Field, value: StoredValue) {
+        if let Some(existing) = self.0.iter_mut().find(|(f, _)| f == field) {
+            existing.1 = value;
+        } else {
+            self.0.push((field.clone(), value));
+        }
+    }
+}
+
+impl field::Visit for StoredFieldsVisitor<'_> {
+    fn record_f64(&mut self, field: &field::Field, value: f64) {
+        self.push(field, StoredValue::F64(value));
+    }
+
+    fn record_i64(&mut self, field: &field::Field, value: i64) {
+        self.push(field, StoredValue::I64(value));
+    }
+
+    fn record_u64(&mut self, field: &field::Field, value: u64) {
+        self.push(field, StoredValue::U64(value));
+    }
+
+    fn record_bool(&mut self, field: &field::Field, value: bool) {
+        self.push(field, StoredValue::Bool(value));
+    }
+
+    fn record_str(&mut self, field: &field::Field, value: &str) {
+        self.push(field, StoredValue::Str(value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+        let rendered = format!("{:?}", value);
+        self.push(field, StoredValue::Rendered(rendered));
+    }
+}
+
 // === impl FmtSubscriber ===
 
 macro_rules! with_event_from_span {
@@ -836,105 +1555,191 @@ where
 
         if extensions.get_mut::<FormattedFields<N>>().is_none() {
             let mut fields = FormattedFields::<N>::new(String::new());
-            if self
-                .fmt_fields
-                .format_fields(fields.as_writer().with_ansi(self.is_ansi), attrs)
-                .is_ok()
-            {
-                fields.was_ansi = self.is_ansi;
-                extensions.insert(fields);
-            } else {
-                eprintln!(
-                    "[tracing-subscriber] Unable to format the following event, ignoring: {:?}",
-                    attrs
-                );
+            let result = match &self.field_redactor {
+                Some(redactor) => format_fields_redacted(
+                    &self.fmt_fields,
+                    fields.as_writer().with_ansi(self.is_ansi),
+                    attrs,
+                    Some(id.clone()),
+                    attrs.metadata(),
+                    redactor,
+                ),
+                None => self
+                    .fmt_fields
+                    .format_fields(fields.as_writer().with_ansi(self.is_ansi), attrs),
+            };
+            match result {
+                Ok(()) => {
+                    fields.was_ansi = self.is_ansi;
+                    extensions.insert(fields);
+                }
+                Err(e) => {
+                    if let Some(handler) = &self.on_internal_error {
+                        handler.0(&InternalError::FormatField(attrs.metadata(), e));
+                    } else {
+                        eprintln!(
+                            "[tracing-subscriber] Unable to format the following event, ignoring: {:?}",
+                            attrs
+                        );
+                    }
+                }
             }
         }
 
+        if self.field_reformatting && extensions.get_mut::<StoredFields>().is_none() {
+            let mut stored = StoredFields::default();
+            stored.record(attrs);
+            extensions.insert(stored);
+        }
+
+        let span_events = self.span_events_for(attrs.metadata());
+
         if self.fmt_span.fmt_timing
-            && self.fmt_span.trace_close()
+            && span_events.contains(FmtSpan::CLOSE)
             && extensions.get_mut::<Timings>().is_none()
         {
             extensions.insert(Timings::new());
         }
 
-        if self.fmt_span.trace_new() {
+        if span_events.contains(FmtSpan::NEW) {
             with_event_from_span!(id, span, "message" = "new", |event| {
                 drop(extensions);
                 drop(span);
-                self.on_event(&event, ctx);
+                self.emit(&event, ctx, Some(FmtSpan::NEW));
             });
         }
     }
 
     fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, C>) {
         let span = ctx.span(id).expect("Span not found, this is a bug");
+        let meta = span.metadata();
         let mut extensions = span.extensions_mut();
+
+        if self.field_reformatting {
+            match extensions.get_mut::<StoredFields>() {
+                Some(stored) => stored.record(values),
+                None => {
+                    let mut stored = StoredFields::default();
+                    stored.record(values);
+                    extensions.insert(stored);
+                }
+            }
+        }
+
         if let Some(fields) = extensions.get_mut::<FormattedFields<N>>() {
-            let _ = self.fmt_fields.add_fields(fields, values);
+            match &self.field_redactor {
+                Some(redactor) => {
+                    if !fields.fields.is_empty() {
+                        fields.fields.push(' ');
+                    }
+                    if let Err(e) = format_fields_redacted(
+                        &self.fmt_fields,
+                        fields.as_writer(),
+                        values,
+                        Some(id.clone()),
+                        meta,
+                        redactor,
+                    ) {
+                        if let Some(handler) = &self.on_internal_error {
+                            handler.0(&InternalError::FormatField(meta, e));
+                        }
+                    }
+                }
+                None => {
+                    if let Err(e) = self.fmt_fields.add_fields(fields, values) {
+                        if let Some(handler) = &self.on_internal_error {
+                            handler.0(&InternalError::FormatField(meta, e));
+                        }
+                    }
+                }
+            }
             return;
         }
 
         let mut fields = FormattedFields::<N>::new(String::new());
-        if self
-            .fmt_fields
-            .format_fields(fields.as_writer().with_ansi(self.is_ansi), values)
-            .is_ok()
-        {
-            fields.was_ansi = self.is_ansi;
-            extensions.insert(fields);
+        let result = match &self.field_redactor {
+            Some(redactor) => format_fields_redacted(
+                &self.fmt_fields,
+                fields.as_writer().with_ansi(self.is_ansi),
+                values,
+                Some(id.clone()),
+                meta,
+                redactor,
+            ),
+            None => self
+                .fmt_fields
+                .format_fields(fields.as_writer().with_ansi(self.is_ansi), values),
+        };
+        match result {
+            Ok(()) => {
+                fields.was_ansi = self.is_ansi;
+                extensions.insert(fields);
+            }
+            Err(e) => {
+                if let Some(handler) = &self.on_internal_error {
+                    handler.0(&InternalError::FormatField(meta, e));
+                }
+            }
         }
     }
 
     fn on_enter(&self, id: &Id, ctx: Context<'_, C>) {
-        if self.fmt_span.trace_enter() || self.fmt_span.trace_close() && self.fmt_span.fmt_timing {
-            let span = ctx.span(id).expect("Span not found, this is a bug");
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let span_events = self.span_events_for(span.metadata());
+        if span_events.contains(FmtSpan::ENTER) || span_events.contains(FmtSpan::CLOSE) {
             let mut extensions = span.extensions_mut();
             if let Some(timings) = extensions.get_mut::<Timings>() {
                 if timings.entered_count == 0 {
                     let now = Instant::now();
                     timings.idle += (now - timings.last).as_nanos() as u64;
                     timings.last = now;
+                    timings.enters += 1;
                 }
                 timings.entered_count += 1;
             }
 
-            if self.fmt_span.trace_enter() {
+            if span_events.contains(FmtSpan::ENTER) {
                 with_event_from_span!(id, span, "message" = "enter", |event| {
                     drop(extensions);
                     drop(span);
-                    self.on_event(&event, ctx);
+                    self.emit(&event, ctx, Some(FmtSpan::ENTER));
                 });
             }
         }
     }
 
     fn on_exit(&self, id: &Id, ctx: Context<'_, C>) {
-        if self.fmt_span.trace_exit() || self.fmt_span.trace_close() && self.fmt_span.fmt_timing {
-            let span = ctx.span(id).expect("Span not found, this is a bug");
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let span_events = self.span_events_for(span.metadata());
+        if span_events.contains(FmtSpan::EXIT) || span_events.contains(FmtSpan::CLOSE) {
             let mut extensions = span.extensions_mut();
             if let Some(timings) = extensions.get_mut::<Timings>() {
                 timings.entered_count -= 1;
                 if timings.entered_count == 0 {
                     let now = Instant::now();
-                    timings.busy += (now - timings.last).as_nanos() as u64;
+                    let busy_period = (now - timings.last).as_nanos() as u64;
+                    timings.busy += busy_period;
+                    if self.span_timing_stats {
+                        timings.busy_min = timings.busy_min.min(busy_period);
+                        timings.busy_max = timings.busy_max.max(busy_period);
+                    }
                     timings.last = now;
                 }
             }
 
-            if self.fmt_span.trace_exit() {
+            if span_events.contains(FmtSpan::EXIT) {
                 with_event_from_span!(id, span, "message" = "exit", |event| {
                     drop(extensions);
                     drop(span);
-                    self.on_event(&event, ctx);
+                    self.emit(&event, ctx, Some(FmtSpan::EXIT));
                 });
             }
         }
     }
 
     fn on_close(&self, id: Id, ctx: Context<'_, C>) {
-        if self.fmt_span.trace_close() {
-            let span = ctx.span(&id).expect("Span not found, this is a bug");
+        let span = ctx.span(&id).expect("Span not found, this is a bug");
+        if self.span_events_for(span.metadata()).contains(FmtSpan::CLOSE) {
             let extensions = span.extensions();
             if let Some(timing) = extensions.get::<Timings>() {
                 let Timings {
@@ -942,43 +1747,130 @@ where
                     mut idle,
                     last,
                     entered_count,
+                    enters,
+                    busy_min,
+                    busy_max,
                 } = *timing;
                 debug_assert_eq!(entered_count, 0);
                 idle += (Instant::now() - last).as_nanos() as u64;
 
                 let t_idle = field::display(TimingDisplay(idle));
-                let t_busy = field::display(TimingDisplay(busy));
-
-                with_event_from_span!(
-                    id,
-                    span,
-                    "message" = "close",
-                    "time.busy" = t_busy,
-                    "time.idle" = t_idle,
-                    |event| {
-                        drop(extensions);
-                        drop(span);
-                        self.on_event(&event, ctx);
+                let t_busy = field::display(TimingDisplay(match self.timing_mode {
+                    TimingMode::BusyIdle => busy,
+                    TimingMode::Total => busy + idle,
+                }));
+
+                if self.span_timing_stats {
+                    // `busy_min` is left at `u64::MAX` if the span was never entered.
+                    let busy_min = if enters == 0 { 0 } else { busy_min };
+                    let t_busy_min = field::display(TimingDisplay(busy_min));
+                    let t_busy_max = field::display(TimingDisplay(busy_max));
+
+                    match self.timing_mode {
+                        TimingMode::BusyIdle => with_event_from_span!(
+                            id,
+                            span,
+                            "message" = "close",
+                            "time.busy" = t_busy,
+                            "time.idle" = t_idle,
+                            "time.enters" = enters,
+                            "time.busy.min" = t_busy_min,
+                            "time.busy.max" = t_busy_max,
+                            |event| {
+                                drop(extensions);
+                                drop(span);
+                                self.emit(&event, ctx, Some(FmtSpan::CLOSE));
+                            }
+                        ),
+                        TimingMode::Total => with_event_from_span!(
+                            id,
+                            span,
+                            "message" = "close",
+                            "time.busy" = t_busy,
+                            "time.enters" = enters,
+                            "time.busy.min" = t_busy_min,
+                            "time.busy.max" = t_busy_max,
+                            |event| {
+                                drop(extensions);
+                                drop(span);
+                                self.emit(&event, ctx, Some(FmtSpan::CLOSE));
+                            }
+                        ),
+                    }
+                } else {
+                    match self.timing_mode {
+                        TimingMode::BusyIdle => with_event_from_span!(
+                            id,
+                            span,
+                            "message" = "close",
+                            "time.busy" = t_busy,
+                            "time.idle" = t_idle,
+                            |event| {
+                                drop(extensions);
+                                drop(span);
+                                self.emit(&event, ctx, Some(FmtSpan::CLOSE));
+                            }
+                        ),
+                        TimingMode::Total => with_event_from_span!(
+                            id,
+                            span,
+                            "message" = "close",
+                            "time.busy" = t_busy,
+                            |event| {
+                                drop(extensions);
+                                drop(span);
+                                self.emit(&event, ctx, Some(FmtSpan::CLOSE));
+                            }
+                        ),
                     }
-                );
+                }
             } else {
                 with_event_from_span!(id, span, "message" = "close", |event| {
                     drop(extensions);
                     drop(span);
-                    self.on_event(&event, ctx);
+                    self.emit(&event, ctx, Some(FmtSpan::CLOSE));
                 });
             }
         }
     }
 
     fn on_event(&self, event: &Event<'_>, ctx: Context<'_, C>) {
-        thread_local! {
-            static BUF: RefCell<String> = const { RefCell::new(String::new()) };
-        }
+        self.emit(event, ctx, None)
+    }
 
-        BUF.with(|buf| {
-            let borrow = buf.try_borrow_mut();
-            let mut a;
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<NonNull<()>> {
+        // This `downcast_raw` impl allows downcasting a `fmt` subscriber to any of
+        // its components (event formatter, field formatter, and `MakeWriter`)
+        // as well as to the subscriber's type itself. The potential use-cases for
+        // this *may* be somewhat niche, though...
+        match () {
+            _ if id == TypeId::of::<Self>() => Some(NonNull::from(self).cast()),
+            _ if id == TypeId::of::<E>() => Some(NonNull::from(&self.fmt_event).cast()),
+            _ if id == TypeId::of::<N>() => Some(NonNull::from(&self.fmt_fields).cast()),
+            _ if id == TypeId::of::<W>() => Some(NonNull::from(&self.make_writer).cast()),
+            _ => None,
+        }
+    }
+}
+
+impl<C, N, E, W> Subscriber<C, N, E, W>
+where
+    C: Collect + for<'a> LookupSpan<'a>,
+    N: for<'writer> FormatFields<'writer> + 'static,
+    E: FormatEvent<C, N> + 'static,
+    W: for<'writer> MakeWriter<'writer> + 'static,
+{
+    /// Formats and writes `event`, using `span_event_kind` to select a
+    /// writer when `event` is a synthesized span lifecycle notification
+    /// rather than a "real" event recorded by the application.
+    fn emit(&self, event: &Event<'_>, ctx: Context<'_, C>, span_event_kind: Option<FmtSpan>) {
+        thread_local! {
+            static BUF: RefCell<String> = const { RefCell::new(String::new()) };
+        }
+
+        BUF.with(|buf| {
+            let borrow = buf.try_borrow_mut();
+            let mut a;
             let mut b;
             let mut buf = match borrow {
                 Ok(buf) => {
@@ -992,49 +1884,53 @@ where
             };
 
             let ctx = self.make_ctx(ctx, event);
-            if self
-                .fmt_event
-                .format_event(
-                    &ctx,
-                    format::Writer::new(&mut buf).with_ansi(self.is_ansi),
-                    event,
-                )
-                .is_ok()
-            {
-                let mut writer = self.make_writer.make_writer_for(event.metadata());
-                let res = io::Write::write_all(&mut writer, buf.as_bytes());
-                if self.log_internal_errors {
-                    if let Err(e) = res {
-                        eprintln!("[tracing-subscriber] Unable to write an event to the Writer for this Subscriber! Error: {}\n", e);
+            let format_result = self.fmt_event.format_event(
+                &ctx,
+                format::Writer::new(&mut buf).with_ansi(self.is_ansi),
+                event,
+            );
+            let writer_for = |meta: &Metadata<'_>| match span_event_kind {
+                Some(kind) => self.writer_for_span_event(meta, kind),
+                None => self.writer_for(meta),
+            };
+            match format_result {
+                Ok(()) => {
+                    if let Some(sink) = &self.sink {
+                        sink.0.on_record(event.metadata(), buf);
+                    } else {
+                        let mut writer = writer_for(event.metadata());
+                        let res = io::Write::write_all(&mut writer, buf.as_bytes());
+                        if let Err(e) = res {
+                            if let Some(handler) = &self.on_internal_error {
+                                handler.0(&InternalError::WriteEvent(event.metadata(), e));
+                            } else if self.log_internal_errors {
+                                eprintln!("[tracing-subscriber] Unable to write an event to the Writer for this Subscriber! Error: {}\n", e);
+                            }
+                        }
                     }
                 }
-            } else if self.log_internal_errors {
-                let err_msg = format!("Unable to format the following event. Name: {}; Fields: {:?}\n",
-                    event.metadata().name(), event.fields());
-                let mut writer = self.make_writer.make_writer_for(event.metadata());
-                let res = io::Write::write_all(&mut writer, err_msg.as_bytes());
-                if let Err(e) = res {
-                    eprintln!("[tracing-subscriber] Unable to write an \"event formatting error\" to the Writer for this Subscriber! Error: {}\n", e);
+                Err(e) => {
+                    if let Some(handler) = &self.on_internal_error {
+                        handler.0(&InternalError::FormatEvent(event.metadata(), e));
+                    } else if self.log_internal_errors {
+                        let err_msg = format!("Unable to format the following event. Name: {}; Fields: {:?}\n",
+                            event.metadata().name(), event.fields());
+                        if let Some(sink) = &self.sink {
+                            sink.0.on_record(event.metadata(), &err_msg);
+                        } else {
+                            let mut writer = writer_for(event.metadata());
+                            let res = io::Write::write_all(&mut writer, err_msg.as_bytes());
+                            if let Err(e) = res {
+                                eprintln!("[tracing-subscriber] Unable to write an \"event formatting error\" to the Writer for this Subscriber! Error: {}\n", e);
+                            }
+                        }
+                    }
                 }
             }
 
             buf.clear();
         });
     }
-
-    unsafe fn downcast_raw(&self, id: TypeId) -> Option<NonNull<()>> {
-        // This `downcast_raw` impl allows downcasting a `fmt` subscriber to any of
-        // its components (event formatter, field formatter, and `MakeWriter`)
-        // as well as to the subscriber's type itself. The potential use-cases for
-        // this *may* be somewhat niche, though...
-        match () {
-            _ if id == TypeId::of::<Self>() => Some(NonNull::from(self).cast()),
-            _ if id == TypeId::of::<E>() => Some(NonNull::from(&self.fmt_event).cast()),
-            _ if id == TypeId::of::<N>() => Some(NonNull::from(&self.fmt_fields).cast()),
-            _ if id == TypeId::of::<W>() => Some(NonNull::from(&self.make_writer).cast()),
-            _ => None,
-        }
-    }
 }
 
 /// Provides the current span context to a formatter.
@@ -1042,6 +1938,7 @@ pub struct FmtContext<'a, C, N> {
     pub(crate) ctx: Context<'a, C>,
     pub(crate) fmt_fields: &'a N,
     pub(crate) event: &'a Event<'a>,
+    pub(crate) field_redactor: Option<&'a FieldRedactor>,
 }
 
 impl<C, N> fmt::Debug for FmtContext<'_, C, N> {
@@ -1060,7 +1957,17 @@ where
         writer: format::Writer<'writer>,
         fields: R,
     ) -> fmt::Result {
-        self.fmt_fields.format_fields(writer, fields)
+        match self.field_redactor {
+            Some(redactor) => format_fields_redacted(
+                self.fmt_fields,
+                writer,
+                fields,
+                self.event.parent().cloned(),
+                self.event.metadata(),
+                redactor,
+            ),
+            None => self.fmt_fields.format_fields(writer, fields),
+        }
     }
 }
 
@@ -1227,6 +2134,63 @@ where
     pub fn field_format(&self) -> &N {
         self.fmt_fields
     }
+
+    /// Re-renders the field values originally recorded on `span` using a
+    /// different [`FormatFields`] implementation, `fmt`.
+    ///
+    /// This allows a single [`Collect`] to drive multiple `fmt` subscribers
+    /// with different output formats (for example, one ANSI terminal
+    /// subscriber and one plain-text file subscriber), each rendering its
+    /// own view of a span's fields, without re-visiting the span's original
+    /// [`Attributes`], which are no longer available once [`on_new_span`]
+    /// returns.
+    ///
+    /// Returns `None` if `span` has no retained field values, either
+    /// because [`Subscriber::with_field_reformatting`] was not enabled when
+    /// they were recorded, or because the span was created before it was
+    /// enabled.
+    ///
+    /// [`Attributes`]: tracing_core::span::Attributes
+    /// [`on_new_span`]: subscribe::Subscribe::on_new_span
+    pub fn reformat_span_fields<N2>(&self, span: &SpanRef<'_, C>, fmt: &N2) -> Option<String>
+    where
+        N2: for<'writer> FormatFields<'writer>,
+    {
+        let extensions = span.extensions();
+        let stored = extensions.get::<StoredFields>()?;
+        let meta = span.metadata();
+        let fields = meta.fields();
+
+        let values: Vec<(&field::Field, Option<&dyn field::Value>)> = stored
+            .values
+            .iter()
+            .map(|(field, value)| (field, Some(value as &dyn field::Value)))
+            .collect();
+        let value_set = fields.value_set(&values);
+        let event = Event::new(meta, &value_set);
+
+        let mut out = String::new();
+        fmt.format_fields(format::Writer::new(&mut out), &event).ok()?;
+        Some(out)
+    }
+}
+
+/// Configures how a `CLOSE` span event reports the time a span spent alive.
+///
+/// Set with [`Subscriber::with_span_timing_mode`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TimingMode {
+    /// Report `time.busy` (time the span was entered) and `time.idle` (time
+    /// between exits and the next enter) as separate fields.
+    ///
+    /// This is the default, and matches the fields emitted by earlier
+    /// versions of this subscriber.
+    #[default]
+    BusyIdle,
+    /// Report a single `time.busy` field containing the span's total
+    /// lifetime, with no idle/busy split.
+    Total,
 }
 
 struct Timings {
@@ -1234,6 +2198,12 @@ struct Timings {
     busy: u64,
     last: Instant,
     entered_count: u64,
+    /// The number of distinct busy periods (outermost enters) this span has had.
+    enters: u64,
+    /// The shortest single busy period (one enter to the matching exit) seen so far.
+    busy_min: u64,
+    /// The longest single busy period (one enter to the matching exit) seen so far.
+    busy_max: u64,
 }
 
 impl Timings {
@@ -1243,6 +2213,9 @@ impl Timings {
             busy: 0,
             last: Instant::now(),
             entered_count: 0,
+            enters: 0,
+            busy_min: u64::MAX,
+            busy_max: 0,
         }
     }
 }
@@ -1669,4 +2642,493 @@ mod test {
             actual.as_str()
         );
     }
+
+    #[test]
+    fn field_redactor_preserves_unmasked_value_kind() {
+        // A `FormatFields` that records which `Visit` method fired for each
+        // field, rather than its rendered text, so that the test can tell a
+        // numeric field from one that was flattened through `Debug`.
+        struct KindFields;
+
+        struct KindVisitor<'a, 'writer>(&'a mut format::Writer<'writer>);
+
+        impl field::Visit for KindVisitor<'_, '_> {
+            fn record_i64(&mut self, field: &field::Field, value: i64) {
+                let _ = write!(self.0, "{}=i64:{} ", field.name(), value);
+            }
+
+            fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+                let _ = write!(self.0, "{}=debug:{:?} ", field.name(), value);
+            }
+        }
+
+        impl<'writer> FormatFields<'writer> for KindFields {
+            fn format_fields<R: RecordFields>(
+                &self,
+                mut writer: format::Writer<'writer>,
+                fields: R,
+            ) -> fmt::Result {
+                fields.record(&mut KindVisitor(&mut writer));
+                Ok(())
+            }
+        }
+
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .fmt_fields(KindFields)
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .without_time()
+            .with_field_redactor(|field, writer| {
+                if field.name() == "secret" {
+                    let _ = write!(writer, "***");
+                    true
+                } else {
+                    false
+                }
+            });
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info!(count = 42, secret = "shh");
+        });
+
+        let actual = make_writer.get_string();
+        // `count` was never masked, so it should still be recorded via
+        // `record_i64`, not flattened into a `Debug`-rendered string.
+        assert!(
+            actual.contains("count=i64:42"),
+            "expected an untouched i64 field, got: {}",
+            actual
+        );
+        assert!(
+            actual.contains("secret=debug:***"),
+            "expected the masked field to carry the redactor's text, got: {}",
+            actual
+        );
+    }
+
+    #[test]
+    fn writer_router_sends_matching_events_to_their_route() {
+        let default_writer = MockMakeWriter::default();
+        let errors_writer = MockMakeWriter::default();
+
+        let router = WriterRouter::new(default_writer.clone())
+            .route(|meta| meta.level() == &tracing::Level::ERROR, errors_writer.clone());
+
+        let subscriber = fmt::Subscriber::default()
+            .with_writer_router(router)
+            .with_ansi(false)
+            .without_time()
+            .with_target(false)
+            .with_level(false);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info!("just passing through");
+            tracing::error!("something broke");
+        });
+
+        assert!(default_writer.get_string().contains("just passing through"));
+        assert!(!default_writer.get_string().contains("something broke"));
+        assert!(errors_writer.get_string().contains("something broke"));
+        assert!(!errors_writer.get_string().contains("just passing through"));
+    }
+
+    #[test]
+    fn on_internal_error_is_called_instead_of_logging_to_stderr() {
+        struct AlwaysError;
+
+        impl std::fmt::Debug for AlwaysError {
+            fn fmt(&self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Err(std::fmt::Error)
+            }
+        }
+
+        let errors = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let errors2 = errors.clone();
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .without_time()
+            .on_internal_error(move |error| {
+                errors2.lock().unwrap().push(format!("{:?}", error));
+            });
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info!(?AlwaysError);
+        });
+
+        // The handler, not the hardcoded `eprintln!`, should have observed
+        // the failure, and nothing should have made it to the writer.
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("FormatEvent"));
+        assert!(make_writer.get_string().is_empty());
+    }
+
+    #[test]
+    fn span_timing_stats_reports_enter_count_and_busy_min_max() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_target(false)
+            .with_level(false)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_span_timing_stats(true);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            let span = tracing::info_span!("my_span");
+            span.in_scope(|| {});
+            span.in_scope(|| {});
+            span.in_scope(|| {});
+            drop(span);
+        });
+
+        let actual = make_writer.get_string();
+        assert!(
+            actual.contains("time.enters=3"),
+            "expected the span to report 3 enters, got: {}",
+            actual
+        );
+        assert!(actual.contains("time.busy.min="));
+        assert!(actual.contains("time.busy.max="));
+    }
+
+    #[test]
+    fn field_reformatting_replays_stored_span_fields() {
+        struct ReformatEvent;
+
+        impl<C, N> FormatEvent<C, N> for ReformatEvent
+        where
+            C: Collect + for<'a> crate::registry::LookupSpan<'a>,
+        {
+            fn format_event(
+                &self,
+                ctx: &FmtContext<'_, C, N>,
+                mut writer: format::Writer<'_>,
+                _event: &Event<'_>,
+            ) -> fmt::Result
+            where
+                N: for<'writer> FormatFields<'writer> + 'static,
+            {
+                if let Some(span) = ctx.lookup_current() {
+                    if let Some(replayed) =
+                        ctx.reformat_span_fields(&span, &format::DefaultFields::default())
+                    {
+                        write!(writer, "{}", replayed)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .event_format(ReformatEvent)
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .without_time()
+            .with_field_reformatting(true);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            let span = tracing::info_span!("my_span", x = 1);
+            span.in_scope(|| {
+                tracing::info!("inside");
+            });
+        });
+
+        assert!(make_writer.get_string().contains("x=1"));
+    }
+
+    #[test]
+    fn field_reformatting_preserves_typed_fields_across_formatters() {
+        struct ReformatEvent;
+
+        impl<C, N> FormatEvent<C, N> for ReformatEvent
+        where
+            C: Collect + for<'a> crate::registry::LookupSpan<'a>,
+        {
+            fn format_event(
+                &self,
+                ctx: &FmtContext<'_, C, N>,
+                mut writer: format::Writer<'_>,
+                _event: &Event<'_>,
+            ) -> fmt::Result
+            where
+                N: for<'writer> FormatFields<'writer> + 'static,
+            {
+                if let Some(span) = ctx.lookup_current() {
+                    if let Some(replayed) =
+                        ctx.reformat_span_fields(&span, &format::JsonFields::default())
+                    {
+                        write!(writer, "{}", replayed)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .event_format(ReformatEvent)
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .without_time()
+            .with_field_reformatting(true);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            let span = tracing::info_span!("my_span", count = 1, enabled = true);
+            span.in_scope(|| {
+                tracing::info!("inside");
+            });
+        });
+
+        let actual = make_writer.get_string();
+        // A typed field replayed into a JSON formatter must keep its type
+        // (`count`/`enabled`), not be flattened to a quoted string as it
+        // would be if `StoredFields` only ever retained `Debug`-rendered text.
+        assert!(actual.contains("\"count\":1"));
+        assert!(actual.contains("\"enabled\":true"));
+    }
+
+    #[test]
+    fn make_writer_for_span_event_routes_by_kind() {
+        #[derive(Clone, Default)]
+        struct KindRoutingWriter {
+            enter_exit: MockMakeWriter,
+            close: MockMakeWriter,
+        }
+
+        impl<'a> MakeWriter<'a> for KindRoutingWriter {
+            type Writer = <MockMakeWriter as MakeWriter<'a>>::Writer;
+
+            fn make_writer(&'a self) -> Self::Writer {
+                self.enter_exit.make_writer()
+            }
+
+            fn make_writer_for_span_event(
+                &'a self,
+                meta: &Metadata<'_>,
+                kind: FmtSpan,
+            ) -> Self::Writer {
+                if kind.contains(FmtSpan::CLOSE) {
+                    self.close.make_writer_for(meta)
+                } else {
+                    self.enter_exit.make_writer_for(meta)
+                }
+            }
+        }
+
+        let make_writer = KindRoutingWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .without_time()
+            .with_target(false)
+            .with_level(false)
+            .with_span_events(FmtSpan::FULL);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info_span!("my_span").in_scope(|| {});
+        });
+
+        let enter_exit = make_writer.enter_exit.get_string();
+        assert!(enter_exit.contains("enter"));
+        assert!(enter_exit.contains("exit"));
+        assert!(!enter_exit.contains("close"));
+        assert!(make_writer.close.get_string().contains("close"));
+    }
+
+    #[test]
+    fn with_span_events_for_overrides_the_default_per_target() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .without_time()
+            .with_target(false)
+            .with_level(false)
+            // No events by default...
+            .with_span_events(FmtSpan::NONE)
+            // ...except for spans under `noisy`, which get full lifecycle logging.
+            .with_span_events_for(|meta| meta.target().starts_with("noisy"), FmtSpan::FULL);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info_span!(target: "quiet::mod", "quiet_span").in_scope(|| {});
+            tracing::info_span!(target: "noisy::mod", "noisy_span").in_scope(|| {});
+        });
+
+        let actual = make_writer.get_string();
+        assert!(!actual.contains("quiet_span"));
+        assert!(actual.contains("new") && actual.contains("noisy_span"));
+        assert!(actual.contains("enter"));
+        assert!(actual.contains("exit"));
+        assert!(actual.contains("close"));
+    }
+
+    #[test]
+    fn span_events_for_override_enables_close_timing_even_when_default_kind_has_none() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_target(false)
+            .with_level(false)
+            // No events by default...
+            .with_span_events(FmtSpan::NONE)
+            // ...except a `close` event, with timing, for spans under `noisy`.
+            .with_span_events_for(|meta| meta.target().starts_with("noisy"), FmtSpan::CLOSE);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info_span!(target: "noisy::mod", "noisy_span").in_scope(|| {});
+        });
+
+        let actual = make_writer.get_string();
+        assert!(actual.contains("close"));
+        assert!(
+            actual.contains("time.busy="),
+            "a span enabled for CLOSE only through a per-target override should still \
+             report time.busy, got: {}",
+            actual
+        );
+        assert!(
+            actual.contains("time.idle="),
+            "a span enabled for CLOSE only through a per-target override should still \
+             report time.idle, got: {}",
+            actual
+        );
+    }
+
+    #[test]
+    fn timing_mode_total_combines_busy_and_idle_into_one_field() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_target(false)
+            .with_level(false)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_span_timing_mode(TimingMode::Total);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info_span!("my_span").in_scope(|| {});
+        });
+
+        let actual = make_writer.get_string();
+        assert!(actual.contains("time.busy="));
+        assert!(
+            !actual.contains("time.idle="),
+            "Total mode should not report a separate time.idle field, got: {}",
+            actual
+        );
+    }
+
+    #[test]
+    fn timing_mode_busy_idle_reports_both_fields_by_default() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_target(false)
+            .with_level(false)
+            .with_span_events(FmtSpan::CLOSE);
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info_span!("my_span").in_scope(|| {});
+        });
+
+        let actual = make_writer.get_string();
+        assert!(actual.contains("time.busy="));
+        assert!(actual.contains("time.idle="));
+    }
+
+    #[test]
+    fn on_format_error_ignores_write_failures() {
+        struct AlwaysError;
+
+        impl std::fmt::Debug for AlwaysError {
+            fn fmt(&self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Err(std::fmt::Error)
+            }
+        }
+
+        struct FailingWriter;
+
+        impl io::Write for FailingWriter {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::Other, "always fails"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let format_errors = Arc::new(std::sync::Mutex::new(0usize));
+        let format_errors2 = format_errors.clone();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(|| FailingWriter)
+            .with_ansi(false)
+            .without_time()
+            .on_format_error(move |_meta, _e| {
+                *format_errors2.lock().unwrap() += 1;
+            });
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            // Succeeds at formatting, but fails to write: on_format_error
+            // should NOT be called for this one.
+            tracing::info!("this formats fine");
+        });
+        assert_eq!(*format_errors.lock().unwrap(), 0);
+
+        let format_errors3 = format_errors.clone();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(|| FailingWriter)
+            .with_ansi(false)
+            .without_time()
+            .on_format_error(move |_meta, _e| {
+                *format_errors3.lock().unwrap() += 1;
+            });
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            // Fails to format (`AlwaysError`'s `Debug` impl always errors):
+            // on_format_error SHOULD be called for this one.
+            tracing::info!(?AlwaysError);
+        });
+        assert_eq!(*format_errors.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn with_sink_routes_formatted_events_to_the_sink_instead_of_the_writer() {
+        #[derive(Clone, Default)]
+        struct RecordingSink(Arc<std::sync::Mutex<Vec<String>>>);
+
+        impl Sink for RecordingSink {
+            fn on_record(&self, _meta: &Metadata<'_>, formatted: &str) {
+                self.0.lock().unwrap().push(formatted.to_string());
+            }
+        }
+
+        let make_writer = MockMakeWriter::default();
+        let sink = RecordingSink::default();
+        let subscriber = fmt::Subscriber::default()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .without_time()
+            .with_target(false)
+            .with_level(false)
+            .with_sink(sink.clone());
+
+        with_default(subscriber.with_collector(Registry::default()), || {
+            tracing::info!("hello from the sink");
+        });
+
+        let recorded = sink.0.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert!(recorded[0].contains("hello from the sink"));
+        assert!(
+            make_writer.get_string().is_empty(),
+            "with_sink should supersede the configured MakeWriter entirely"
+        );
+    }
 }
@@ -0,0 +1,276 @@
+use std::fmt;
+
+use tracing_core::{
+    field::{Field, Visit},
+    Collect, Event,
+};
+
+use super::{FormatEvent, FormatFields, Writer};
+use crate::field::RecordFields;
+use crate::fmt::fmt_subscriber::{FmtContext, FormattedFields};
+use crate::registry::LookupSpan;
+
+/// Marker type for the [logfmt] event [`Format`](super::Format).
+///
+/// `logfmt` renders each event as a single line of `key=value` pairs
+/// (quoting values that contain whitespace, a `"`, or an `=`), which is
+/// convenient both for
+/// humans scanning logs and for tools like `grep`/`awk` that expect one
+/// record per line.
+///
+/// [logfmt]: https://brandur.org/logfmt
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Logfmt {
+    _p: (),
+}
+
+/// The [`FormatFields`] implementation used by [`Logfmt`].
+///
+/// Like [`Logfmt`] itself, this renders fields as `key=value` pairs,
+/// quoting any value whose `Display`/`Debug` representation contains
+/// whitespace, a `"`, or an `=`.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct LogfmtFields {
+    _p: (),
+}
+
+impl LogfmtFields {
+    /// Returns a new [`LogfmtFields`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'writer> FormatFields<'writer> for LogfmtFields {
+    fn format_fields<R: RecordFields>(&self, mut writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = LogfmtVisitor::new(&mut writer);
+        fields.record(&mut visitor);
+        visitor.finish()
+    }
+}
+
+struct LogfmtVisitor<'a, 'writer> {
+    writer: &'a mut Writer<'writer>,
+    is_empty: bool,
+    result: fmt::Result,
+}
+
+impl<'a, 'writer> LogfmtVisitor<'a, 'writer> {
+    fn new(writer: &'a mut Writer<'writer>) -> Self {
+        Self {
+            writer,
+            is_empty: true,
+            result: Ok(()),
+        }
+    }
+
+    fn maybe_pad(&mut self) {
+        if self.is_empty {
+            self.is_empty = false;
+        } else {
+            self.result = write!(self.writer, " ");
+        }
+    }
+
+    fn write_value(&mut self, name: &str, value: &dyn fmt::Display) {
+        if self.result.is_err() {
+            return;
+        }
+        self.maybe_pad();
+        let rendered = value.to_string();
+        self.result = if needs_quoting(&rendered) {
+            write!(self.writer, "{}={:?}", name, rendered)
+        } else {
+            write!(self.writer, "{}={}", name, rendered)
+        };
+    }
+
+    fn finish(self) -> fmt::Result {
+        self.result
+    }
+}
+
+/// Returns `true` if `rendered` needs to be quoted (and backslash-escaped
+/// via `{:?}`) to round-trip as a single logfmt value: it contains
+/// whitespace (which would otherwise split it into multiple `key=value`
+/// pairs), a `"` (which would prematurely open/close a quoted value), or a
+/// `=` (which would otherwise be parsed as the start of a new key).
+fn needs_quoting(rendered: &str) -> bool {
+    rendered.contains(char::is_whitespace) || rendered.contains(['"', '='])
+}
+
+impl Visit for LogfmtVisitor<'_, '_> {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.write_value(field.name(), &value);
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.write_value(field.name(), &value);
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.write_value(field.name(), &value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.write_value(field.name(), &value);
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.write_value(field.name(), &value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.result.is_err() {
+            return;
+        }
+        self.maybe_pad();
+        let rendered = format!("{:?}", value);
+        self.result = if needs_quoting(&rendered) {
+            write!(self.writer, "{}={:?}", field.name(), rendered)
+        } else {
+            write!(self.writer, "{}={}", field.name(), rendered)
+        };
+    }
+}
+
+impl<C, N, T> FormatEvent<C, N> for super::Format<Logfmt, T>
+where
+    C: Collect + for<'a> LookupSpan<'a>,
+    T: crate::fmt::time::FormatTime,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, C, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result
+    where
+        N: for<'writer> FormatFields<'writer> + 'static,
+    {
+        use crate::fmt::time::FormatTime;
+
+        let meta = event.metadata();
+        if self.display_timestamp {
+            write!(writer, "timestamp=")?;
+            self.timer.format_time(&mut writer)?;
+            write!(writer, " ")?;
+        }
+        if self.display_level {
+            write!(writer, "level={} ", meta.level().to_string().to_ascii_lowercase())?;
+        }
+        if self.display_target {
+            write!(writer, "target={} ", meta.target())?;
+        }
+
+        ctx.visit_spans(|span| {
+            let extensions = span.extensions();
+            if let Some(fields) = extensions.get::<FormattedFields<N>>() {
+                write_prefixed(&mut writer, &fields.fields, "span.")?;
+            }
+            Ok(())
+        })?;
+
+        let ansi = writer.has_ansi_escapes();
+        ctx.format_fields(Writer::new(&mut writer).with_ansi(ansi), event)?;
+        writeln!(writer)
+    }
+}
+
+/// Rewrites an already-rendered logfmt `key=value key2=value2 ...` blob,
+/// prefixing every key with `prefix`.
+///
+/// Splits on spaces that are not inside a (possibly backslash-escaped)
+/// quoted value, so that a quoted value containing whitespace isn't
+/// mistaken for a boundary between fields.
+fn write_prefixed(writer: &mut Writer<'_>, rendered: &str, prefix: &str) -> fmt::Result {
+    let mut in_quotes = false;
+    let mut prev_backslash = false;
+    let mut start = 0;
+
+    for (i, ch) in rendered.char_indices() {
+        match ch {
+            '"' if !prev_backslash => in_quotes = !in_quotes,
+            ' ' if !in_quotes => {
+                if i > start {
+                    write!(writer, "{}{} ", prefix, &rendered[start..i])?;
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+        prev_backslash = ch == '\\' && !prev_backslash;
+    }
+    if start < rendered.len() {
+        write!(writer, "{}{} ", prefix, &rendered[start..])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fmt::{self, test::MockMakeWriter};
+
+    #[test]
+    fn logfmt_quotes_values_containing_whitespace() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .without_time()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_target(false)
+            .with_level(false)
+            .logfmt();
+        let subscriber = subscriber.with_collector(crate::Registry::default());
+        tracing::collect::with_default(subscriber, || {
+            tracing::info!(plain = 42, spaced = "two words", "hello");
+        });
+
+        let actual = make_writer.get_string();
+        assert!(actual.contains("plain=42"));
+        assert!(actual.contains(r#"spaced="two words""#));
+    }
+
+    #[test]
+    fn logfmt_escapes_embedded_quotes_and_equals_signs() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .without_time()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_target(false)
+            .with_level(false)
+            .logfmt();
+        let subscriber = subscriber.with_collector(crate::Registry::default());
+        tracing::collect::with_default(subscriber, || {
+            tracing::info!(quoted = "has \"quotes\"", kv = "a=b", "hello");
+        });
+
+        let actual = make_writer.get_string();
+        assert!(actual.contains(r#"quoted="has \"quotes\"""#));
+        assert!(actual.contains(r#"kv="a=b""#));
+    }
+
+    #[test]
+    fn logfmt_flattens_span_fields_with_a_span_prefix() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = fmt::Subscriber::default()
+            .without_time()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_target(false)
+            .with_level(false)
+            .logfmt();
+        let subscriber = subscriber.with_collector(crate::Registry::default());
+        tracing::collect::with_default(subscriber, || {
+            let span = tracing::info_span!("request", request_id = 42);
+            let _enter = span.enter();
+            tracing::info!("handled");
+        });
+
+        let actual = make_writer.get_string();
+        assert!(actual.contains("span.request_id=42"));
+        assert!(actual.contains("message=handled"));
+    }
+}
@@ -0,0 +1,592 @@
+//! Formatters for logging `tracing` events.
+use std::fmt::{self, Debug, Display};
+use std::ops::BitOr;
+
+use tracing_core::{
+    field::{Field, Visit},
+    Collect, Event,
+};
+
+use crate::field::RecordFields;
+use crate::fmt::fmt_subscriber::{FmtContext, FormattedFields};
+use crate::registry::LookupSpan;
+
+mod json;
+pub use json::{Json, JsonFields};
+
+mod logfmt;
+pub use logfmt::{Logfmt, LogfmtFields};
+
+/// A writer to which formatted representations of spans and events are
+/// written.
+///
+/// This is a thin wrapper around a [`fmt::Write`] implementation, which
+/// additionally tracks whether ANSI terminal formatting is enabled.
+pub struct Writer<'writer> {
+    writer: &'writer mut dyn fmt::Write,
+    is_ansi: bool,
+}
+
+impl<'writer> Writer<'writer> {
+    /// Returns a new `Writer` that writes to the provided `impl fmt::Write`.
+    pub fn new(writer: &'writer mut impl fmt::Write) -> Self {
+        Self {
+            writer,
+            is_ansi: false,
+        }
+    }
+
+    /// Sets whether ANSI terminal formatting escape codes should be emitted.
+    pub fn with_ansi(self, ansi: bool) -> Self {
+        Self {
+            is_ansi: ansi,
+            ..self
+        }
+    }
+
+    /// Returns whether ANSI terminal formatting escape codes should be
+    /// emitted by this writer.
+    pub fn has_ansi_escapes(&self) -> bool {
+        self.is_ansi
+    }
+
+    /// Writes a string slice into this writer.
+    pub fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_str(s)
+    }
+
+    /// Writes a [`char`] into this writer.
+    pub fn write_char(&mut self, c: char) -> fmt::Result {
+        self.writer.write_char(c)
+    }
+
+    /// Writes formatted data into this writer, so that `write!` can be used
+    /// with a `Writer` without importing [`fmt::Write`].
+    pub fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        self.writer.write_fmt(args)
+    }
+}
+
+impl fmt::Write for Writer<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.writer.write_char(c)
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        self.writer.write_fmt(args)
+    }
+}
+
+/// A type that can format a tracing [`Event`] for a [`FormatEvent`]
+/// implementation.
+pub trait FormatEvent<C, N>
+where
+    C: Collect + for<'a> LookupSpan<'a>,
+{
+    /// Writes a formatted representation of `event` to `writer`.
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, C, N>,
+        writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result
+    where
+        N: for<'writer> FormatFields<'writer> + 'static;
+}
+
+/// A type that can format a set of [`tracing::field::Field`] values recorded
+/// on a span or event.
+pub trait FormatFields<'writer> {
+    /// Format the given fields into `writer`.
+    fn format_fields<R: RecordFields>(&self, writer: Writer<'writer>, fields: R) -> fmt::Result;
+
+    /// Record additional fields onto a span that has already had fields
+    /// formatted into `current`.
+    fn add_fields(
+        &self,
+        current: &'writer mut FormattedFields<Self>,
+        fields: &tracing_core::span::Record<'_>,
+    ) -> fmt::Result
+    where
+        Self: Sized,
+    {
+        let mut writer = current.as_writer();
+        if !current.fields.is_empty() {
+            writer.write_char(' ')?;
+        }
+        self.format_fields(writer, fields)
+    }
+}
+
+/// The default [`FormatFields`] implementation, which formats fields as
+/// `key=value`, separated by spaces.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct DefaultFields {
+    _p: (),
+}
+
+impl DefaultFields {
+    /// Returns a new default [`FormatFields`] implementation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'writer> FormatFields<'writer> for DefaultFields {
+    fn format_fields<R: RecordFields>(&self, writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = DefaultVisitor::new(writer, true);
+        fields.record(&mut visitor);
+        visitor.finish()
+    }
+}
+
+struct DefaultVisitor<'a> {
+    writer: Writer<'a>,
+    is_empty: bool,
+    result: fmt::Result,
+}
+
+impl<'a> DefaultVisitor<'a> {
+    fn new(writer: Writer<'a>, is_empty: bool) -> Self {
+        Self {
+            writer,
+            is_empty,
+            result: Ok(()),
+        }
+    }
+
+    fn maybe_pad(&mut self) {
+        if self.is_empty {
+            self.is_empty = false;
+        } else {
+            self.result = write!(self.writer, " ");
+        }
+    }
+
+    fn finish(self) -> fmt::Result {
+        self.result
+    }
+}
+
+impl Visit for DefaultVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if self.result.is_err() {
+            return;
+        }
+        self.maybe_pad();
+        self.result = if field.name() == "message" {
+            write!(self.writer, "{}", value)
+        } else {
+            write!(self.writer, "{}={}", field.name(), value)
+        };
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if self.result.is_err() {
+            return;
+        }
+        self.maybe_pad();
+        self.result = if field.name() == "message" {
+            write!(self.writer, "{:?}", value)
+        } else {
+            write!(self.writer, "{}={:?}", field.name(), value)
+        };
+    }
+}
+
+/// Marker type for the default, human-readable [`Format`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Full;
+
+/// Marker type for a more compact version of the [`Full`] format.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Compact;
+
+/// An excessively pretty, human-readable event formatter.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Pretty {
+    _p: (),
+}
+
+impl<'writer> FormatFields<'writer> for Pretty {
+    fn format_fields<R: RecordFields>(&self, writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = DefaultVisitor::new(writer, true);
+        fields.record(&mut visitor);
+        visitor.finish()
+    }
+}
+
+/// A pre-configured event formatter.
+#[derive(Debug, Clone)]
+pub struct Format<L = Full, T = crate::fmt::time::SystemTime> {
+    pub(crate) timer: T,
+    pub(crate) display_timestamp: bool,
+    pub(crate) display_target: bool,
+    pub(crate) display_level: bool,
+    pub(crate) display_thread_id: bool,
+    pub(crate) display_thread_name: bool,
+    pub(crate) display_filename: bool,
+    pub(crate) display_line_number: bool,
+    pub(crate) _format: std::marker::PhantomData<fn(L)>,
+}
+
+impl Default for Format<Full, crate::fmt::time::SystemTime> {
+    fn default() -> Self {
+        Self {
+            timer: crate::fmt::time::SystemTime,
+            display_timestamp: true,
+            display_target: true,
+            display_level: true,
+            display_thread_id: false,
+            display_thread_name: false,
+            display_filename: false,
+            display_line_number: false,
+            _format: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<L, T> Format<L, T> {
+    /// Use the given [`timer`][`crate::fmt::time::FormatTime`] for timestamps
+    /// instead of the default.
+    pub fn with_timer<T2>(self, timer: T2) -> Format<L, T2> {
+        Format {
+            timer,
+            display_timestamp: self.display_timestamp,
+            display_target: self.display_target,
+            display_level: self.display_level,
+            display_thread_id: self.display_thread_id,
+            display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            _format: std::marker::PhantomData,
+        }
+    }
+
+    /// Do not emit timestamps with spans and events.
+    pub fn without_time(self) -> Format<L, ()> {
+        Format {
+            timer: (),
+            display_timestamp: false,
+            display_target: self.display_target,
+            display_level: self.display_level,
+            display_thread_id: self.display_thread_id,
+            display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            _format: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets whether the event's target is displayed.
+    pub fn with_target(self, display_target: bool) -> Self {
+        Self {
+            display_target,
+            ..self
+        }
+    }
+
+    /// Sets whether the event's source file name is displayed.
+    pub fn with_file(self, display_filename: bool) -> Self {
+        Self {
+            display_filename,
+            ..self
+        }
+    }
+
+    /// Sets whether the event's source line number is displayed.
+    pub fn with_line_number(self, display_line_number: bool) -> Self {
+        Self {
+            display_line_number,
+            ..self
+        }
+    }
+
+    /// Sets whether the event's level is displayed.
+    pub fn with_level(self, display_level: bool) -> Self {
+        Self {
+            display_level,
+            ..self
+        }
+    }
+
+    /// Sets whether the thread ID of the thread the event occurred on is displayed.
+    pub fn with_thread_ids(self, display_thread_id: bool) -> Self {
+        Self {
+            display_thread_id,
+            ..self
+        }
+    }
+
+    /// Sets whether the thread name of the thread the event occurred on is displayed.
+    pub fn with_thread_names(self, display_thread_name: bool) -> Self {
+        Self {
+            display_thread_name,
+            ..self
+        }
+    }
+
+    /// Use the [`Compact`] formatter, a less verbose variant of [`Full`].
+    pub fn compact(self) -> Format<Compact, T> {
+        Format {
+            timer: self.timer,
+            display_timestamp: self.display_timestamp,
+            display_target: self.display_target,
+            display_level: self.display_level,
+            display_thread_id: self.display_thread_id,
+            display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            _format: std::marker::PhantomData,
+        }
+    }
+
+    /// Use the excessively pretty, human-readable [`Pretty`] formatter.
+    pub fn pretty(self) -> Format<Pretty, T> {
+        Format {
+            timer: self.timer,
+            display_timestamp: self.display_timestamp,
+            display_target: self.display_target,
+            display_level: self.display_level,
+            display_thread_id: self.display_thread_id,
+            display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            _format: std::marker::PhantomData,
+        }
+    }
+
+    /// Use the JSON [`Json`] formatter.
+    pub fn json(self) -> Format<Json, T> {
+        Format {
+            timer: self.timer,
+            display_timestamp: self.display_timestamp,
+            display_target: self.display_target,
+            display_level: self.display_level,
+            display_thread_id: self.display_thread_id,
+            display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            _format: std::marker::PhantomData,
+        }
+    }
+
+    /// Use the [`Logfmt`] formatter.
+    pub fn logfmt(self) -> Format<Logfmt, T> {
+        Format {
+            timer: self.timer,
+            display_timestamp: self.display_timestamp,
+            display_target: self.display_target,
+            display_level: self.display_level,
+            display_thread_id: self.display_thread_id,
+            display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            _format: std::marker::PhantomData,
+        }
+    }
+}
+
+fn format_level_and_target<L, T>(
+    format: &Format<L, T>,
+    writer: &mut Writer<'_>,
+    event: &Event<'_>,
+) -> fmt::Result {
+    use crate::fmt::time::FormatTime;
+
+    if format.display_timestamp {
+        format.timer.format_time(writer)?;
+        write!(writer, " ")?;
+    }
+    let meta = event.metadata();
+    if format.display_level {
+        write!(writer, "{} ", meta.level())?;
+    }
+    if format.display_target {
+        write!(writer, "{}: ", meta.target())?;
+    }
+    if format.display_filename {
+        if let Some(file) = meta.file() {
+            write!(writer, "{}", file)?;
+            if format.display_line_number {
+                if let Some(line) = meta.line() {
+                    write!(writer, ":{}", line)?;
+                }
+            }
+            write!(writer, ": ")?;
+        }
+    }
+    Ok(())
+}
+
+impl<C, N, T> FormatEvent<C, N> for Format<Full, T>
+where
+    C: Collect + for<'a> LookupSpan<'a>,
+    T: crate::fmt::time::FormatTime,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, C, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result
+    where
+        N: for<'writer> FormatFields<'writer> + 'static,
+    {
+        format_level_and_target(self, &mut writer, event)?;
+        let ansi = writer.has_ansi_escapes();
+        ctx.format_fields(Writer::new(&mut writer).with_ansi(ansi), event)?;
+        writeln!(writer)
+    }
+}
+
+impl<C, N, T> FormatEvent<C, N> for Format<Compact, T>
+where
+    C: Collect + for<'a> LookupSpan<'a>,
+    T: crate::fmt::time::FormatTime,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, C, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result
+    where
+        N: for<'writer> FormatFields<'writer> + 'static,
+    {
+        format_level_and_target(self, &mut writer, event)?;
+        let ansi = writer.has_ansi_escapes();
+        ctx.format_fields(Writer::new(&mut writer).with_ansi(ansi), event)?;
+        writeln!(writer)
+    }
+}
+
+impl<C, N, T> FormatEvent<C, N> for Format<Pretty, T>
+where
+    C: Collect + for<'a> LookupSpan<'a>,
+    T: crate::fmt::time::FormatTime,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, C, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result
+    where
+        N: for<'writer> FormatFields<'writer> + 'static,
+    {
+        format_level_and_target(self, &mut writer, event)?;
+        let ansi = writer.has_ansi_escapes();
+        ctx.format_fields(Writer::new(&mut writer).with_ansi(ansi), event)?;
+        writeln!(writer)
+    }
+}
+
+/// Which [`Event`]s are synthesized for span lifecycle notifications.
+///
+/// See [`Subscriber::with_span_events`][crate::fmt::Subscriber::with_span_events]
+/// for details.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FmtSpan(u8);
+
+#[allow(non_upper_case_globals)]
+impl FmtSpan {
+    /// No events will be synthesized when spans are created, entered, exited, or closed.
+    pub const NONE: FmtSpan = FmtSpan(0);
+    /// An event will be synthesized when spans are created.
+    pub const NEW: FmtSpan = FmtSpan(1 << 0);
+    /// An event will be synthesized when spans are entered.
+    pub const ENTER: FmtSpan = FmtSpan(1 << 1);
+    /// An event will be synthesized when spans are exited.
+    pub const EXIT: FmtSpan = FmtSpan(1 << 2);
+    /// An event will be synthesized when a span closes.
+    pub const CLOSE: FmtSpan = FmtSpan(1 << 3);
+    /// Events will be synthesized when spans are entered or exited.
+    pub const ACTIVE: FmtSpan = FmtSpan(FmtSpan::ENTER.0 | FmtSpan::EXIT.0);
+    /// Events will be synthesized whenever a span is created, entered, exited, or closed.
+    pub const FULL: FmtSpan =
+        FmtSpan(FmtSpan::NEW.0 | FmtSpan::ENTER.0 | FmtSpan::EXIT.0 | FmtSpan::CLOSE.0);
+
+    /// Returns `true` if `self` contains all the flags set in `other`.
+    pub fn contains(&self, other: FmtSpan) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl BitOr for FmtSpan {
+    type Output = FmtSpan;
+
+    fn bitor(self, rhs: FmtSpan) -> Self::Output {
+        FmtSpan(self.0 | rhs.0)
+    }
+}
+
+/// The configuration for which span lifecycle events are synthesized,
+/// and whether timing information is recorded.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FmtSpanConfig {
+    pub(crate) kind: FmtSpan,
+    pub(crate) fmt_timing: bool,
+}
+
+impl FmtSpanConfig {
+    pub(crate) fn with_kind(self, kind: FmtSpan) -> Self {
+        Self { kind, ..self }
+    }
+
+    pub(crate) fn without_time(self) -> Self {
+        Self {
+            fmt_timing: false,
+            ..self
+        }
+    }
+}
+
+impl Default for FmtSpanConfig {
+    fn default() -> Self {
+        Self {
+            kind: FmtSpan::NONE,
+            fmt_timing: true,
+        }
+    }
+}
+
+/// Renders a span's busy/idle time, in nanoseconds, as a human-readable
+/// duration (e.g. `1.23ms`).
+pub(crate) struct TimingDisplay(pub(crate) u64);
+
+impl Display for TimingDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut t = self.0 as f64;
+        for unit in ["ns", "µs", "ms", "s"].iter() {
+            if t < 10.0 {
+                return write!(f, "{:.2}{}", t, unit);
+            } else if t < 100.0 {
+                return write!(f, "{:.1}{}", t, unit);
+            } else if t < 1000.0 {
+                return write!(f, "{:.0}{}", t, unit);
+            }
+            t /= 1000.0;
+        }
+        write!(f, "{:.0}s", t * 1000.0)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::Writer;
+    use crate::fmt::time::FormatTime;
+    use std::fmt;
+
+    pub(crate) struct MockTime;
+
+    impl FormatTime for MockTime {
+        fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+            write!(w, "fake time")
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::{fmt, io, sync::Arc};
+
+use tracing_core::Metadata;
+
+/// A failure encountered while formatting or writing a `tracing` event or
+/// span, passed to a callback registered with
+/// [`Subscriber::on_internal_error`][crate::fmt::Subscriber::on_internal_error].
+///
+/// These errors are unlikely and generally indicate a bug in a
+/// [`FormatEvent`][crate::fmt::FormatEvent]/[`FormatFields`][crate::fmt::FormatFields]
+/// implementation, in a field's `Debug` or `Display` implementation, or in
+/// the configured [`MakeWriter`][crate::fmt::MakeWriter].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InternalError<'a> {
+    /// A span's fields could not be formatted.
+    FormatField(&'a Metadata<'static>, fmt::Error),
+    /// An event could not be formatted.
+    FormatEvent(&'a Metadata<'static>, fmt::Error),
+    /// A formatted event could not be written to the configured writer.
+    WriteEvent(&'a Metadata<'static>, io::Error),
+}
+
+/// A callback registered with
+/// [`Subscriber::on_internal_error`][crate::fmt::Subscriber::on_internal_error].
+#[derive(Clone)]
+pub(crate) struct InternalErrorHandler(pub(crate) Arc<dyn Fn(&InternalError<'_>) + Send + Sync>);
+
+impl fmt::Debug for InternalErrorHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("InternalErrorHandler(..)")
+    }
+}
@@ -0,0 +1,73 @@
+//! A `Subscriber` for formatting and logging `tracing` data.
+use std::io;
+
+use tracing_core::Collect;
+
+use crate::registry::{LookupSpan, Registry};
+
+pub mod format;
+pub mod time;
+pub mod writer;
+
+mod fmt_subscriber;
+mod internal_error;
+mod sink;
+mod writer_router;
+
+pub use fmt_subscriber::{FmtContext, FormattedFields, Subscriber};
+pub use format::{FormatEvent, FormatFields};
+pub use internal_error::InternalError;
+pub use sink::Sink;
+pub use writer::{BoxMakeWriter, MakeWriter, TestWriter};
+pub use writer_router::WriterRouter;
+
+// Re-exported so that code within this module (and its tests) can refer to
+// `crate::fmt::subscribe::Subscribe` in addition to `crate::subscribe::Subscribe`.
+pub(crate) use crate::subscribe;
+
+/// A type alias for a "fully assembled" formatting collector, combining a
+/// [`Subscriber`] with a [`Registry`] to track span context.
+///
+/// Unlike [`Subscriber`], which is a [`Subscribe`][crate::subscribe::Subscribe]
+/// that must be composed with a collector capable of tracking spans (such as
+/// [`Registry`]), a `Collector` is ready to use as a complete
+/// [`Collect`](tracing_core::Collect) implementation on its own, e.g. with
+/// [`tracing::collect::set_global_default`].
+pub type Collector<N = format::DefaultFields, E = format::Format, W = fn() -> io::Stdout> =
+    Subscriber<Registry, N, E, W>;
+
+impl Subscriber<Registry> {
+    /// Returns a new [`Subscriber`] builder for constructing a [`Collector`].
+    pub fn builder() -> Self {
+        Self::default().log_internal_errors(true)
+    }
+}
+
+impl<N, E, W> Subscriber<Registry, N, E, W>
+where
+    N: for<'writer> FormatFields<'writer> + 'static,
+    E: FormatEvent<Registry, N> + 'static,
+    W: for<'writer> MakeWriter<'writer> + 'static,
+{
+    /// Finishes configuring this builder, returning a [`Collector`] that can
+    /// be set as the default collector, e.g. via
+    /// [`tracing::collect::set_global_default`].
+    pub fn finish(self) -> impl Collect {
+        use crate::subscribe::Subscribe;
+
+        self.with_collector(Registry::default())
+    }
+}
+
+/// Returns a new [`Subscriber`] with the default configuration.
+///
+/// This can be composed with other [`Subscribe`](crate::subscribe::Subscribe)s
+/// and [`Collect`]s to build a subscriber.
+pub fn fmt() -> Subscriber<Registry> {
+    Subscriber::builder()
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    pub(crate) use super::writer::test::{MockMakeWriter, MockWriter};
+}
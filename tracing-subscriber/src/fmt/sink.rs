@@ -0,0 +1,43 @@
+use std::{fmt, sync::Arc};
+
+use tracing_core::Metadata;
+
+/// A sink for already-formatted `tracing` events and span lifecycle
+/// notifications, as a higher-level alternative to
+/// [`MakeWriter`][crate::fmt::MakeWriter].
+///
+/// A [`MakeWriter`][crate::fmt::MakeWriter] hands back a raw [`io::Write`],
+/// which is a good fit for line-oriented text but forces batching,
+/// rotation, and async-flush concerns onto the writer, which then has to
+/// parse bytes back out if it wants to make decisions based on level or
+/// target. A `Sink` instead receives each record already rendered by this
+/// subscriber's [`FormatEvent`][crate::fmt::FormatEvent], together with its
+/// [`Metadata`], so that integrations such as size/time-based file
+/// rotation, a ring buffer for crash-dump capture, or forwarding to a
+/// channel can decide what to do with a record without re-parsing it.
+///
+/// Configured on a [`Subscriber`][crate::fmt::Subscriber] with
+/// [`Subscriber::with_sink`][crate::fmt::Subscriber::with_sink].
+///
+/// [`io::Write`]: std::io::Write
+pub trait Sink: Send + Sync + 'static {
+    /// Receives a single formatted event or span lifecycle notification.
+    ///
+    /// `meta` is the [`Metadata`] of the event, or, for a synthesized span
+    /// lifecycle notification, of the span it was synthesized from.
+    /// `formatted` is the exact text produced by this subscriber's
+    /// [`FormatEvent`][crate::fmt::FormatEvent] implementation, including
+    /// its trailing newline, if any.
+    fn on_record(&self, meta: &Metadata<'_>, formatted: &str);
+}
+
+/// A type-erased [`Sink`], as configured via
+/// [`Subscriber::with_sink`][crate::fmt::Subscriber::with_sink].
+#[derive(Clone)]
+pub(crate) struct SinkHandle(pub(crate) Arc<dyn Sink>);
+
+impl fmt::Debug for SinkHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SinkHandle(..)")
+    }
+}
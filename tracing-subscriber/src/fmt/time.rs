@@ -0,0 +1,72 @@
+//! Formatters for event timestamps.
+use std::fmt;
+use std::time::Instant;
+
+use super::format::Writer;
+
+/// A type that can measure and format the current time.
+///
+/// This trait is used by [`Format`] to print a timestamp for every [`Event`]
+/// it formats, unless the format has been configured to omit timestamps via
+/// [`Format::without_time`].
+///
+/// [`Format`]: super::format::Format
+/// [`Event`]: tracing_core::Event
+/// [`Format::without_time`]: super::format::Format::without_time
+pub trait FormatTime {
+    /// Write the current time to the given [`Writer`].
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result;
+}
+
+impl<'a, F> FormatTime for &'a F
+where
+    F: FormatTime,
+{
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        (*self).format_time(w)
+    }
+}
+
+/// Retrieve and print the current wall-clock time.
+#[derive(Clone, Debug, Default)]
+pub struct SystemTime;
+
+impl FormatTime for SystemTime {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        write!(w, "{}.{:06}", now.as_secs(), now.subsec_micros())
+    }
+}
+
+/// Retrieve and print the relative elapsed wall-clock time since an epoch.
+///
+/// The `Uptime` can be constructed with either [`Uptime::default`], which
+/// uses [`Instant::now`] as the epoch, or [`Uptime::from`], which constructs
+/// an `Uptime` that uses an arbitrary [`Instant`] as the epoch.
+#[derive(Clone, Debug)]
+pub struct Uptime {
+    epoch: Instant,
+}
+
+impl Default for Uptime {
+    fn default() -> Self {
+        Self {
+            epoch: Instant::now(),
+        }
+    }
+}
+
+impl From<Instant> for Uptime {
+    fn from(epoch: Instant) -> Self {
+        Self { epoch }
+    }
+}
+
+impl FormatTime for Uptime {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        let e = self.epoch.elapsed();
+        write!(w, "{}.{:06}s", e.as_secs(), e.subsec_micros())
+    }
+}
@@ -0,0 +1,254 @@
+//! Abstractions for creating [`io::Write`] instances.
+//!
+//! This module contains the [`MakeWriter`] trait, which allows a
+//! [`Subscriber`](super::Subscriber) to choose a [writer] on a per-event (or
+//! per-span-notification) basis, and a couple of [`MakeWriter`]
+//! implementations that cover the most common cases.
+//!
+//! [writer]: std::io::Write
+use std::fmt;
+use std::io;
+
+use tracing_core::Metadata;
+
+use super::format::FmtSpan;
+
+/// A type that can create [`io::Write`] instances.
+///
+/// `MakeWriter` is used by [`fmt::Subscriber`] to print formatted text
+/// representations of [`Event`]s.
+///
+/// This trait is already implemented for function pointers and closures that
+/// return an instance of [`io::Write`], such as [`io::Stdout`] and
+/// [`io::Stderr`].
+///
+/// [`fmt::Subscriber`]: super::Subscriber
+/// [`Event`]: tracing_core::Event
+pub trait MakeWriter<'a> {
+    /// The concrete [`io::Write`] implementation returned by [`make_writer`].
+    ///
+    /// [`make_writer`]: MakeWriter::make_writer
+    type Writer: io::Write;
+
+    /// Returns an instance of [`Writer`].
+    ///
+    /// [`Writer`]: MakeWriter::Writer
+    fn make_writer(&'a self) -> Self::Writer;
+
+    /// Returns a [`Writer`] for writing data from the span or event
+    /// described by the provided [`Metadata`].
+    ///
+    /// By default, this calls [`self.make_writer()`][Self::make_writer],
+    /// ignoring the provided metadata, but `MakeWriter` implementations
+    /// that compose multiple writers may use the metadata to select
+    /// between them.
+    ///
+    /// [`Writer`]: MakeWriter::Writer
+    fn make_writer_for(&'a self, _meta: &Metadata<'_>) -> Self::Writer {
+        self.make_writer()
+    }
+
+    /// Returns a [`Writer`] for writing a synthesized span lifecycle
+    /// notification (as configured with
+    /// [`with_span_events`][crate::fmt::Subscriber::with_span_events]), of
+    /// the given `kind`, described by the provided [`Metadata`].
+    ///
+    /// By default, this forwards to
+    /// [`make_writer_for`][Self::make_writer_for], treating span
+    /// notifications the same as ordinary events. Implementations that
+    /// want to route span lifecycle notifications to a different sink
+    /// than other events (for example, sending `CLOSE` notifications,
+    /// which carry timing data, to a metrics sink) can override this
+    /// method.
+    ///
+    /// [`Writer`]: MakeWriter::Writer
+    fn make_writer_for_span_event(&'a self, meta: &Metadata<'_>, _kind: FmtSpan) -> Self::Writer {
+        self.make_writer_for(meta)
+    }
+}
+
+impl<'a, F, W> MakeWriter<'a> for F
+where
+    F: Fn() -> W,
+    W: io::Write,
+{
+    type Writer = W;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        (self)()
+    }
+}
+
+/// A writer intended to support [`libtest`'s output capturing][capturing] for use in unit tests.
+///
+/// `TestWriter` is used to enable capturing support for `fmt` subscribers
+/// when used in unit tests, so as not to mangle the test output.
+///
+/// [capturing]: https://doc.rust-lang.org/book/ch11-02-running-tests.html#showing-function-output
+#[derive(Default, Debug)]
+pub struct TestWriter {
+    _p: (),
+}
+
+impl TestWriter {
+    /// Returns a new `TestWriter` with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl io::Write for TestWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let s = std::str::from_utf8(buf).unwrap_or("<invalid UTF-8>");
+        print!("{}", s);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for TestWriter {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        Self::default()
+    }
+}
+
+/// A type implementing [`MakeWriter`] that has type-erased the concrete
+/// [`io::Write`] type it makes, boxing it instead.
+///
+/// This is useful in cases where the concrete [`MakeWriter`] type is not
+/// able to be named, or when constructing a type erased `MakeWriter` trait
+/// object.
+pub struct BoxMakeWriter {
+    inner: Box<dyn ErasedMakeWriter>,
+}
+
+impl BoxMakeWriter {
+    /// Constructs a `BoxMakeWriter` wrapping a type implementing [`MakeWriter`].
+    pub fn new<M>(make_writer: M) -> Self
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+        for<'a> <M as MakeWriter<'a>>::Writer: 'static,
+    {
+        Self {
+            inner: Box::new(make_writer),
+        }
+    }
+}
+
+impl fmt::Debug for BoxMakeWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("BoxMakeWriter { .. }")
+    }
+}
+
+impl<'a> MakeWriter<'a> for BoxMakeWriter {
+    type Writer = Box<dyn io::Write>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.inner.make_writer()
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        self.inner.make_writer_for(meta)
+    }
+
+    fn make_writer_for_span_event(&'a self, meta: &Metadata<'_>, kind: FmtSpan) -> Self::Writer {
+        self.inner.make_writer_for_span_event(meta, kind)
+    }
+}
+
+impl<M> From<M> for BoxMakeWriter
+where
+    M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    for<'a> <M as MakeWriter<'a>>::Writer: 'static,
+{
+    fn from(make_writer: M) -> Self {
+        Self::new(make_writer)
+    }
+}
+
+trait ErasedMakeWriter: Send + Sync {
+    fn make_writer(&self) -> Box<dyn io::Write>;
+    fn make_writer_for(&self, meta: &Metadata<'_>) -> Box<dyn io::Write>;
+    fn make_writer_for_span_event(&self, meta: &Metadata<'_>, kind: FmtSpan) -> Box<dyn io::Write>;
+}
+
+impl<M> ErasedMakeWriter for M
+where
+    M: for<'a> MakeWriter<'a> + Send + Sync,
+    for<'a> <M as MakeWriter<'a>>::Writer: 'static,
+{
+    fn make_writer(&self) -> Box<dyn io::Write> {
+        Box::new(MakeWriter::make_writer(self))
+    }
+
+    fn make_writer_for(&self, meta: &Metadata<'_>) -> Box<dyn io::Write> {
+        Box::new(MakeWriter::make_writer_for(self, meta))
+    }
+
+    fn make_writer_for_span_event(&self, meta: &Metadata<'_>, kind: FmtSpan) -> Box<dyn io::Write> {
+        Box::new(MakeWriter::make_writer_for_span_event(self, meta, kind))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::io;
+    use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+
+    use super::MakeWriter;
+
+    pub(crate) struct MockWriter {
+        buf: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl MockWriter {
+        pub(crate) fn new(buf: Arc<Mutex<Vec<u8>>>) -> Self {
+            Self { buf }
+        }
+
+        fn map_err<Guard>(err: TryLockError<Guard>) -> io::Error {
+            io::Error::new(io::ErrorKind::Other, err.to_string())
+        }
+
+        fn buf(&self) -> io::Result<MutexGuard<'_, Vec<u8>>> {
+            self.buf.try_lock().map_err(Self::map_err)
+        }
+    }
+
+    impl io::Write for MockWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.buf()?.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.buf()?.flush()
+        }
+    }
+
+    /// A [`MakeWriter`] that writes to an in-memory buffer, for use in tests.
+    #[derive(Clone, Default)]
+    pub(crate) struct MockMakeWriter {
+        buf: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl MockMakeWriter {
+        pub(crate) fn get_string(&self) -> String {
+            let buf = self.buf.lock().expect("lock the buffer");
+            String::from_utf8(buf.to_vec()).expect("writer produced invalid utf-8")
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for MockMakeWriter {
+        type Writer = MockWriter;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            MockWriter::new(self.buf.clone())
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::{fmt, io};
+
+use tracing_core::Metadata;
+
+use super::{format::FmtSpan, writer::BoxMakeWriter, MakeWriter};
+
+/// Routes events and span lifecycle notifications to one of several
+/// [`MakeWriter`]s, selected by their [`Metadata`].
+///
+/// Configured on a [`Subscriber`][crate::fmt::Subscriber] with
+/// [`Subscriber::with_writer_router`][crate::fmt::Subscriber::with_writer_router].
+/// This allows a single `fmt::Subscriber` to send, for example, `ERROR`
+/// events to stderr, events from `http::*` targets to an access-log file,
+/// and everything else to stdout, all while sharing one event formatter and
+/// span store.
+pub struct WriterRouter {
+    routes: Vec<(
+        Box<dyn Fn(&Metadata<'_>) -> bool + Send + Sync>,
+        BoxMakeWriter,
+    )>,
+    default: BoxMakeWriter,
+}
+
+impl WriterRouter {
+    /// Returns a new router that sends every event to `default` until a
+    /// [route][Self::route] is added that matches it.
+    pub fn new(default: impl Into<BoxMakeWriter>) -> Self {
+        Self {
+            routes: Vec::new(),
+            default: default.into(),
+        }
+    }
+
+    /// Adds a route: events and spans whose [`Metadata`] matches `filter`
+    /// are sent to `writer` rather than the default.
+    ///
+    /// Routes are consulted in the order they were added; the first
+    /// matching route wins.
+    pub fn route(
+        mut self,
+        filter: impl Fn(&Metadata<'_>) -> bool + Send + Sync + 'static,
+        writer: impl Into<BoxMakeWriter>,
+    ) -> Self {
+        self.routes.push((Box::new(filter), writer.into()));
+        self
+    }
+
+    pub(crate) fn make_writer_for<'a>(&'a self, meta: &Metadata<'_>) -> Box<dyn io::Write + 'a> {
+        for (filter, writer) in &self.routes {
+            if filter(meta) {
+                return writer.make_writer_for(meta);
+            }
+        }
+        self.default.make_writer_for(meta)
+    }
+
+    /// Like [`make_writer_for`][Self::make_writer_for], but for a span
+    /// lifecycle notification of the given `kind` rather than an event.
+    ///
+    /// Routes are still selected by `meta` alone; `kind` is forwarded to the
+    /// matching (or default) [`MakeWriter`] so that it can choose a
+    /// different writer for, say, `CLOSE` than for `ENTER`/`EXIT`.
+    pub(crate) fn make_writer_for_span_event<'a>(
+        &'a self,
+        meta: &Metadata<'_>,
+        kind: FmtSpan,
+    ) -> Box<dyn io::Write + 'a> {
+        for (filter, writer) in &self.routes {
+            if filter(meta) {
+                return writer.make_writer_for_span_event(meta, kind);
+            }
+        }
+        self.default.make_writer_for_span_event(meta, kind)
+    }
+}
+
+impl fmt::Debug for WriterRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterRouter")
+            .field("routes", &self.routes.len())
+            .field("default", &self.default)
+            .finish()
+    }
+}
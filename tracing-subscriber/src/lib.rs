@@ -51,7 +51,9 @@
 //!   (enabled by default).
 //! - `alloc`: Depend on [`liballoc`] (enabled by "std").
 //! - `env-filter`: Enables the [`EnvFilter`] type, which implements filtering
-//!   similar to the [`env_logger` crate]. **Requires "std"**.
+//!   similar to the [`env_logger` crate]. **Requires "std"**. Directives are
+//!   fixed at construction/parse time; there is currently no `reload`-based
+//!   API for mutating a live `EnvFilter`'s directives in place.
 //! - `fmt`: Enables the [`fmt`] module, which provides a subscriber
 //!   implementation for printing formatted representations of trace events.
 //!   Enabled by default. **Requires "registry" and "std"**.
@@ -103,6 +105,12 @@
 //! tracing-subscriber = { version = "0.3", default-features = false, features = ["alloc"] }
 //! ```
 //!
+//! Note that the [`registry`] module, and therefore [`fmt`] and any other
+//! `Subscribe` that needs to track per-span state, still requires "std"
+//! today: there is no `alloc`-only `Registry` backend yet, so storing
+//! per-span fields on bare metal isn't currently possible. `no_std` users
+//! are limited to the bare [`Subscribe`] trait until such a backend exists.
+//!
 //! ## Supported Rust Versions
 //!
 //! Tracing is built against the latest stable release. The minimum supported